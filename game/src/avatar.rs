@@ -0,0 +1,241 @@
+use std::collections::{HashMap, VecDeque};
+use bevy::prelude::*;
+use bevy_spacetimedb::{
+    DeleteEvent, ReadDeleteEvent, ReadInsertEvent, ReadUpdateEvent, StdbConnection, StdbDisconnectedEvent,
+};
+use spacetimedb_sdk::{DbContext, Identity};
+
+use crate::stdb::{DbConnection, StdbPlayer};
+
+/// How far behind the newest update avatars are drawn, in seconds. A little over the client
+/// send interval, so there is nearly always a later sample to interpolate towards.
+const INTERPOLATION_DELAY: f64 = 0.15;
+/// Longest an avatar keeps moving past its newest sample before it stops and waits.
+const MAX_EXTRAPOLATION: f64 = 0.25;
+/// Samples kept per avatar.
+const BUFFER_LEN: usize = 8;
+/// Avatars are positioned at the player's eye; the body hangs below it.
+const EYE_HEIGHT: f32 = 1.6;
+
+pub struct AvatarPlugin;
+
+impl Plugin for AvatarPlugin {
+    fn build(&self, app: &mut App) {
+        // insert and update events are registered by the player plugin
+        app.init_resource::<Avatars>()
+            .add_event::<DeleteEvent<StdbPlayer>>()
+            .add_systems(Startup, setup_avatar_assets)
+            .add_systems(Update, (
+                on_avatar_insert,
+                on_avatar_update,
+                on_avatar_delete,
+                despawn_avatars_on_disconnect,
+                interpolate_avatars,
+            ).chain());
+    }
+}
+
+/// Mesh and material every avatar shares.
+#[derive(Resource)]
+pub struct AvatarAssets {
+    mesh: Handle<Mesh>,
+    material: Handle<StandardMaterial>,
+}
+
+/// Spawned avatar for each remote player that's online.
+#[derive(Resource, Default)]
+pub struct Avatars(pub HashMap<Identity, Entity>);
+
+/// A transform received from the server, stamped with the server's time.
+#[derive(Clone, Copy)]
+struct Sample {
+    time: f64,
+    position: Vec3,
+    rotation: Quat,
+}
+
+/// Jitter buffer of a remote player's recent transforms.
+#[derive(Component)]
+pub struct Avatar {
+    samples: VecDeque<Sample>,
+    /// Server time minus local time for the fastest update seen, so late arrivals don't pull
+    /// the playback clock back.
+    clock_offset: Option<f64>,
+}
+
+impl Avatar {
+    fn push(&mut self, sample: Sample, now: f64) {
+        if self.samples.back().is_some_and(|last| last.time >= sample.time) {
+            return;
+        }
+        let offset = sample.time - now;
+        self.clock_offset = Some(self.clock_offset.map_or(offset, |o| o.max(offset)));
+        if self.samples.len() == BUFFER_LEN {
+            self.samples.pop_front();
+        }
+        self.samples.push_back(sample);
+    }
+
+    /// Position and rotation at local time `now`, or `None` before any sample has arrived.
+    fn sample_at(&self, now: f64) -> Option<(Vec3, Quat)> {
+        let (first, last) = (self.samples.front()?, self.samples.back()?);
+        let time = now + self.clock_offset? - INTERPOLATION_DELAY;
+        if time <= first.time {
+            return Some((first.position, first.rotation));
+        }
+
+        if time >= last.time {
+            // keep going the way it was heading for a moment, then hold
+            let Some(previous) = self.samples.iter().rev().nth(1) else {
+                return Some((last.position, last.rotation));
+            };
+            let ahead = (time - last.time).min(MAX_EXTRAPOLATION);
+            let velocity = (last.position - previous.position) / (last.time - previous.time) as f32;
+            return Some((last.position + velocity * ahead as f32, last.rotation));
+        }
+
+        let (a, b) = self.samples.iter()
+            .zip(self.samples.iter().skip(1))
+            .find(|(_, b)| b.time >= time)?;
+        let t = ((time - a.time) / (b.time - a.time)) as f32;
+        Some((a.position.lerp(b.position, t), a.rotation.slerp(b.rotation, t)))
+    }
+}
+
+fn sample_of(player: &StdbPlayer) -> Sample {
+    let (p, r) = (&player.transform.position, &player.transform.rotation);
+    Sample {
+        time: player.last_update.to_micros_since_unix_epoch() as f64 / 1_000_000.0,
+        position: Vec3::new(p.x, p.y, p.z),
+        rotation: Quat::from_xyzw(r.x, r.y, r.z, r.w),
+    }
+}
+
+fn is_remote(stdb: &StdbConnection<DbConnection>, player: &StdbPlayer) -> bool {
+    stdb.conn().try_identity() != Some(player.identity)
+}
+
+pub fn setup_avatar_assets(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+) {
+    // a 1.9 block capsule centred on the origin, lowered so its feet are `EYE_HEIGHT` below the eye
+    let mesh = Capsule3d::new(0.35, 1.2).mesh().build().translated_by(Vec3::Y * (0.95 - EYE_HEIGHT));
+    commands.insert_resource(AvatarAssets {
+        mesh: meshes.add(mesh),
+        material: materials.add(StandardMaterial {
+            base_color: Color::srgb(0.85, 0.55, 0.2),
+            perceptual_roughness: 0.6,
+            ..default()
+        }),
+    });
+}
+
+fn spawn_avatar(
+    player: &StdbPlayer,
+    now: f64,
+    assets: &AvatarAssets,
+    avatars: &mut Avatars,
+    commands: &mut Commands,
+) {
+    let sample = sample_of(player);
+    let mut avatar = Avatar { samples: VecDeque::with_capacity(BUFFER_LEN), clock_offset: None };
+    avatar.push(sample, now);
+    let entity = commands.spawn((
+        Mesh3d(assets.mesh.clone()),
+        MeshMaterial3d(assets.material.clone()),
+        Transform::from_translation(sample.position),
+        Name::new(player.name.clone()),
+        avatar,
+    )).id();
+    if let Some(old) = avatars.0.insert(player.identity, entity) {
+        commands.entity(old).despawn();
+    }
+}
+
+fn despawn_avatar(identity: &Identity, avatars: &mut Avatars, commands: &mut Commands) {
+    if let Some(entity) = avatars.0.remove(identity) {
+        commands.entity(entity).despawn();
+    }
+}
+
+pub fn on_avatar_insert(
+    mut events: ReadInsertEvent<StdbPlayer>,
+    stdb: Res<StdbConnection<DbConnection>>,
+    time: Res<Time>,
+    assets: Res<AvatarAssets>,
+    mut avatars: ResMut<Avatars>,
+    mut commands: Commands,
+) {
+    for event in events.read() {
+        let player = &event.row;
+        if player.online && is_remote(&stdb, player) {
+            spawn_avatar(player, time.elapsed_secs_f64(), &assets, &mut avatars, &mut commands);
+        }
+    }
+}
+
+/// Feeds moves into the avatar's buffer, and spawns or despawns it as the player comes and goes.
+pub fn on_avatar_update(
+    mut events: ReadUpdateEvent<StdbPlayer>,
+    stdb: Res<StdbConnection<DbConnection>>,
+    time: Res<Time>,
+    assets: Res<AvatarAssets>,
+    mut avatars: ResMut<Avatars>,
+    mut avatar_q: Query<&mut Avatar>,
+    mut commands: Commands,
+) {
+    let now = time.elapsed_secs_f64();
+    for event in events.read() {
+        let player = &event.new;
+        if !is_remote(&stdb, player) {
+            continue;
+        }
+        if !player.online {
+            despawn_avatar(&player.identity, &mut avatars, &mut commands);
+            continue;
+        }
+        match avatars.0.get(&player.identity).and_then(|&entity| avatar_q.get_mut(entity).ok()) {
+            Some(mut avatar) => avatar.push(sample_of(player), now),
+            None => spawn_avatar(player, now, &assets, &mut avatars, &mut commands),
+        }
+    }
+}
+
+/// Rows leave the cache when the subscription ends.
+pub fn on_avatar_delete(
+    mut events: ReadDeleteEvent<StdbPlayer>,
+    mut avatars: ResMut<Avatars>,
+    mut commands: Commands,
+) {
+    for event in events.read() {
+        despawn_avatar(&event.row.identity, &mut avatars, &mut commands);
+    }
+}
+
+/// Without a connection nobody's updates arrive, so don't leave them standing around.
+pub fn despawn_avatars_on_disconnect(
+    mut events: EventReader<StdbDisconnectedEvent>,
+    mut avatars: ResMut<Avatars>,
+    mut commands: Commands,
+) {
+    if events.read().next().is_none() {
+        return;
+    }
+    for (_, entity) in avatars.0.drain() {
+        commands.entity(entity).despawn();
+    }
+}
+
+pub fn interpolate_avatars(time: Res<Time>, mut avatar_q: Query<(&Avatar, &mut Transform)>) {
+    let now = time.elapsed_secs_f64();
+    for (avatar, mut transform) in avatar_q.iter_mut() {
+        let Some((position, rotation)) = avatar.sample_at(now) else {
+            continue;
+        };
+        transform.translation = position;
+        // the body only turns about the vertical; pitch is just where they're looking
+        transform.rotation = Quat::from_rotation_y(rotation.to_euler(EulerRot::YXZ).0);
+    }
+}
@@ -13,6 +13,9 @@ use stdb::DbConnection;
 mod player;
 use player::PlayerPlugin;
 
+mod avatar;
+use avatar::AvatarPlugin;
+
 mod terrain;
 use terrain::TerrainPlugin;
 
@@ -45,7 +48,8 @@ fn main() {
                 conn.run_threaded();
                 conn
             })
-            .with_events(|_plugin, _app, _db, _reducers| {
+            .with_events(|_plugin, app, db, _reducers| {
+                terrain::systems::register_packed_index(app, db);
                 // tables!(
                     // heightmap_chunk,
                     // mesh_chunk,
@@ -57,6 +61,7 @@ fn main() {
         )
         .add_plugins(DefaultPlugins)
         .add_plugins(PlayerPlugin)
+        .add_plugins(AvatarPlugin)
         .add_plugins(TerrainPlugin)
         .add_systems(Startup, setup)
         .add_systems(Update, on_connected)
@@ -6,20 +6,46 @@ use bevy::{
     ui::{UiRect, PositionType, JustifyContent},
 };
 use bevy::pbr::Atmosphere;
+use std::collections::VecDeque;
+
+use bevy_spacetimedb::{InsertEvent, ReadInsertEvent, ReadUpdateEvent, StdbConnectedEvent, StdbConnection, UpdateEvent};
+use spacetimedb_sdk::{DbContext, Table, Timestamp};
+
+use crate::stdb::{
+    move_player, DbConnection, StdbPlayer, StdbPosition, StdbRotation, StdbTransform,
+    player_table::PlayerTableAccess,
+};
+
+/// Seconds between transform updates sent to the server.
+const SEND_INTERVAL: f32 = 0.1;
+/// Smallest change in position, in blocks, or rotation, in radians, worth sending.
+const SEND_EPSILON: f32 = 1e-3;
+/// Sent positions kept to recognise the server echoing them back.
+const SENT_HISTORY: usize = 16;
+/// How far the server's position may be from everything we sent before we take its word for it.
+const CORRECTION_DISTANCE: f32 = 0.05;
+/// Farther than this from the server's position we rejoin it before sending; the server rejects
+/// moves longer than 32 blocks outright, so we'd otherwise never be accepted again.
+const RESYNC_DISTANCE: f32 = 24.0;
 
 pub struct PlayerPlugin;
 
 impl Plugin for PlayerPlugin {
     fn build(&self, app: &mut App) {
         app.init_resource::<PlayerSettings>()
+            .init_resource::<MoveSender>()
+            .add_event::<InsertEvent<StdbPlayer>>()
+            .add_event::<UpdateEvent<StdbPlayer>>()
             .add_systems(Startup, (setup_player, setup_ui))
-            .add_systems(Update, (player_move, player_look, toggle_cursor, update_position_text).chain());
+            .add_systems(Update, (player_move, player_look, toggle_cursor, update_position_text).chain())
+            .add_systems(Update, (subscribe_players, on_own_player_insert, on_own_player_update, send_player_transform).chain());
     }
 }
 
 #[derive(Resource)]
 pub struct PlayerSettings {
     pub sensitivity: f32,
+    /// Blocks per second; the server's `MAX_SPEED` caps it, so raise both together.
     pub speed: f32,
 }
 
@@ -289,4 +315,115 @@ fn toggle_cursor(
             }
         }
     }
-} 
\ No newline at end of file
+}
+
+/// Throttles transform updates to the server and remembers what was sent.
+#[derive(Resource)]
+pub struct MoveSender {
+    timer: Timer,
+    last_sent: Option<(Vec3, Quat)>,
+    recent: VecDeque<Vec3>,
+}
+
+impl Default for MoveSender {
+    fn default() -> Self {
+        Self {
+            timer: Timer::from_seconds(SEND_INTERVAL, TimerMode::Repeating),
+            last_sent: None,
+            recent: VecDeque::with_capacity(SENT_HISTORY),
+        }
+    }
+}
+
+fn to_stdb_transform(transform: &Transform) -> StdbTransform {
+    let (t, r) = (transform.translation, transform.rotation);
+    StdbTransform {
+        position: StdbPosition { x: t.x, y: t.y, z: t.z },
+        rotation: StdbRotation { x: r.x, y: r.y, z: r.z, w: r.w },
+    }
+}
+
+fn position_of(player: &StdbPlayer) -> Vec3 {
+    let p = &player.transform.position;
+    Vec3::new(p.x, p.y, p.z)
+}
+
+fn is_own(stdb: &StdbConnection<DbConnection>, player: &StdbPlayer) -> bool {
+    stdb.conn().try_identity() == Some(player.identity)
+}
+
+/// Subscribes to every player on connect; our own row is how the server corrects us.
+fn subscribe_players(mut events: EventReader<StdbConnectedEvent>, stdb: Res<StdbConnection<DbConnection>>) {
+    for _ in events.read() {
+        stdb.subscribe()
+            .on_error(|_, e| error!("Player sub error: {}", e))
+            .subscribe("SELECT * FROM player");
+    }
+}
+
+/// Our row arriving means we've (re)joined: start where the server has us.
+fn on_own_player_insert(
+    mut events: ReadInsertEvent<StdbPlayer>,
+    stdb: Res<StdbConnection<DbConnection>>,
+    mut sender: ResMut<MoveSender>,
+    mut query: Query<&mut Transform, With<PlayerController>>,
+) {
+    let Ok(mut transform) = query.single_mut() else { return; };
+    for event in events.read().filter(|event| is_own(&stdb, &event.row)) {
+        transform.translation = position_of(&event.row);
+        sender.last_sent = None;
+        sender.recent.clear();
+    }
+}
+
+/// Snaps back when the server clamped a move, i.e. its position isn't one we sent.
+fn on_own_player_update(
+    mut events: ReadUpdateEvent<StdbPlayer>,
+    stdb: Res<StdbConnection<DbConnection>>,
+    mut sender: ResMut<MoveSender>,
+    mut query: Query<&mut Transform, With<PlayerController>>,
+) {
+    let Ok(mut transform) = query.single_mut() else { return; };
+    for event in events.read().filter(|event| is_own(&stdb, &event.new)) {
+        let position = position_of(&event.new);
+        if sender.recent.iter().all(|sent| sent.distance(position) > CORRECTION_DISTANCE) {
+            transform.translation = position;
+            sender.recent.clear();
+        }
+    }
+}
+
+/// Sends the camera's transform at most every `SEND_INTERVAL`, and only when it has changed.
+fn send_player_transform(
+    time: Res<Time>,
+    stdb: Res<StdbConnection<DbConnection>>,
+    mut sender: ResMut<MoveSender>,
+    mut query: Query<&mut Transform, With<PlayerController>>,
+) {
+    if !sender.timer.tick(time.delta()).just_finished() || !stdb.conn().is_active() {
+        return;
+    }
+    let Ok(mut transform) = query.single_mut() else { return; };
+    if let Some(own) = stdb.db().player().iter().find(|player| is_own(&stdb, player)) {
+        if position_of(&own).distance(transform.translation) > RESYNC_DISTANCE {
+            transform.translation = position_of(&own);
+            sender.recent.clear();
+        }
+    }
+    let unchanged = sender.last_sent.is_some_and(|(position, rotation)| {
+        position.distance(transform.translation) < SEND_EPSILON && rotation.angle_between(transform.rotation) < SEND_EPSILON
+    });
+    if unchanged {
+        return;
+    }
+
+    if let Err(e) = stdb.conn().reducers.move_player(to_stdb_transform(&transform), Timestamp::now()) {
+        error!("Failed to send player transform: {}", e);
+        return;
+    }
+    sender.last_sent = Some((transform.translation, transform.rotation));
+    if sender.recent.len() == SENT_HISTORY {
+        sender.recent.pop_front();
+    }
+    sender.recent.push_back(transform.translation);
+}
@@ -8,15 +8,19 @@ use super::xz_coords_type::XzCoords;
 
 #[derive(__lib::ser::Serialize, __lib::de::Deserialize, Clone, PartialEq, Debug)]
 #[sats(crate = __lib)]
-pub struct ChunkVertex {
+pub struct ChunkProp {
+    pub id: u64,
     pub grid: XzCoords,
     pub grid_x: i32,
     pub grid_z: i32,
-    pub heightmap: Vec<f32>,
-    pub vertices: Vec<f32>,
-    pub normals: Vec<f32>,
+    pub kind: u32,
+    pub x: f32,
+    pub y: f32,
+    pub z: f32,
+    pub rotation: f32,
+    pub scale: f32,
 }
 
-impl __sdk::InModule for ChunkVertex {
+impl __sdk::InModule for ChunkProp {
     type Module = super::RemoteModule;
 }
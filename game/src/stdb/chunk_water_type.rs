@@ -8,14 +8,15 @@ use super::xz_coords_type::XzCoords;
 
 #[derive(__lib::ser::Serialize, __lib::de::Deserialize, Clone, PartialEq, Debug)]
 #[sats(crate = __lib)]
-pub struct ChunkMesh {
+pub struct ChunkWater {
+    pub key: i64,
     pub grid: XzCoords,
     pub grid_x: i32,
     pub grid_z: i32,
-    pub indices: Vec<u32>,
-    pub materials: Vec<u32>,
+    pub sea_level: f32,
+    pub mask: Vec<u8>,
 }
 
-impl __sdk::InModule for ChunkMesh {
+impl __sdk::InModule for ChunkWater {
     type Module = super::RemoteModule;
 }
@@ -4,40 +4,64 @@
 #![allow(unused, clippy::all)]
 use spacetimedb_sdk::__codegen::{self as __sdk, __lib, __sats, __ws};
 
-pub mod chunk_mesh_table;
-pub mod chunk_mesh_type;
-pub mod chunk_vertex_table;
-pub mod chunk_vertex_type;
+pub mod chunk_packed_table;
+pub mod chunk_packed_type;
+pub mod chunk_prop_table;
+pub mod chunk_prop_type;
 pub mod identity_connected_reducer;
 pub mod identity_disconnected_reducer;
+pub mod chunk_water_table;
+pub mod chunk_water_type;
 pub mod material_definition_table;
 pub mod material_definition_type;
 pub mod mesh_table;
 pub mod mesh_type;
+pub mod move_player_reducer;
 pub mod on_chunk_requested_reducer;
 pub mod on_material_defined_reducer;
+pub mod on_view_moved_reducer;
+pub mod player_table;
+pub mod request_chunk_region_reducer;
+pub mod stdb_player_type;
+pub mod stdb_position_type;
+pub mod stdb_rotation_type;
+pub mod stdb_transform_type;
 pub mod xz_coords_type;
 
-pub use chunk_mesh_table::*;
-pub use chunk_mesh_type::ChunkMesh;
-pub use chunk_vertex_table::*;
-pub use chunk_vertex_type::ChunkVertex;
+pub use chunk_packed_table::*;
+pub use chunk_packed_type::ChunkPacked;
+pub use chunk_prop_table::*;
+pub use chunk_prop_type::ChunkProp;
 pub use identity_connected_reducer::{
     identity_connected, set_flags_for_identity_connected, IdentityConnectedCallbackId,
 };
 pub use identity_disconnected_reducer::{
     identity_disconnected, set_flags_for_identity_disconnected, IdentityDisconnectedCallbackId,
 };
+pub use chunk_water_table::*;
+pub use chunk_water_type::ChunkWater;
 pub use material_definition_table::*;
 pub use material_definition_type::MaterialDefinition;
 pub use mesh_table::*;
 pub use mesh_type::Mesh;
+pub use move_player_reducer::{move_player, set_flags_for_move_player, MovePlayerCallbackId};
 pub use on_chunk_requested_reducer::{
     on_chunk_requested, set_flags_for_on_chunk_requested, OnChunkRequestedCallbackId,
 };
 pub use on_material_defined_reducer::{
     on_material_defined, set_flags_for_on_material_defined, OnMaterialDefinedCallbackId,
 };
+pub use on_view_moved_reducer::{
+    on_view_moved, set_flags_for_on_view_moved, OnViewMovedCallbackId,
+};
+pub use player_table::*;
+pub use request_chunk_region_reducer::{
+    request_chunk_region, set_flags_for_request_chunk_region, RequestChunkRegionCallbackId,
+};
+pub use stdb_player_type::StdbPlayer;
+pub use stdb_position_type::StdbPosition;
+pub use stdb_rotation_type::StdbRotation;
+pub use stdb_transform_type::StdbTransform;
 pub use xz_coords_type::XzCoords;
 
 #[derive(Clone, PartialEq, Debug)]
@@ -50,8 +74,14 @@ pub use xz_coords_type::XzCoords;
 pub enum Reducer {
     IdentityConnected,
     IdentityDisconnected,
+    MovePlayer {
+        transform: StdbTransform,
+        client_time: __sdk::Timestamp,
+    },
     OnChunkRequested { coord: XzCoords },
     OnMaterialDefined { e: MaterialDefinition },
+    OnViewMoved { from: XzCoords, to: XzCoords },
+    RequestChunkRegion { min: XzCoords, max: XzCoords },
 }
 
 impl __sdk::InModule for Reducer {
@@ -63,8 +93,11 @@ impl __sdk::Reducer for Reducer {
         match self {
             Reducer::IdentityConnected => "identity_connected",
             Reducer::IdentityDisconnected => "identity_disconnected",
+            Reducer::MovePlayer { .. } => "move_player",
             Reducer::OnChunkRequested { .. } => "on_chunk_requested",
             Reducer::OnMaterialDefined { .. } => "on_material_defined",
+            Reducer::OnViewMoved { .. } => "on_view_moved",
+            Reducer::RequestChunkRegion { .. } => "request_chunk_region",
         }
     }
 }
@@ -80,6 +113,10 @@ impl TryFrom<__ws::ReducerCallInfo<__ws::BsatnFormat>> for Reducer {
                 identity_disconnected_reducer::IdentityDisconnectedArgs,
             >("identity_disconnected", &value.args)?
             .into()),
+            "move_player" => Ok(__sdk::parse_reducer_args::<
+                move_player_reducer::MovePlayerArgs,
+            >("move_player", &value.args)?
+            .into()),
             "on_chunk_requested" => Ok(__sdk::parse_reducer_args::<
                 on_chunk_requested_reducer::OnChunkRequestedArgs,
             >("on_chunk_requested", &value.args)?
@@ -88,6 +125,14 @@ impl TryFrom<__ws::ReducerCallInfo<__ws::BsatnFormat>> for Reducer {
                 on_material_defined_reducer::OnMaterialDefinedArgs,
             >("on_material_defined", &value.args)?
             .into()),
+            "on_view_moved" => Ok(__sdk::parse_reducer_args::<
+                on_view_moved_reducer::OnViewMovedArgs,
+            >("on_view_moved", &value.args)?
+            .into()),
+            "request_chunk_region" => Ok(__sdk::parse_reducer_args::<
+                request_chunk_region_reducer::RequestChunkRegionArgs,
+            >("request_chunk_region", &value.args)?
+            .into()),
             unknown => {
                 Err(
                     __sdk::InternalError::unknown_name("reducer", unknown, "ReducerCallInfo")
@@ -102,10 +147,12 @@ impl TryFrom<__ws::ReducerCallInfo<__ws::BsatnFormat>> for Reducer {
 #[allow(non_snake_case)]
 #[doc(hidden)]
 pub struct DbUpdate {
-    chunk_mesh: __sdk::TableUpdate<ChunkMesh>,
-    chunk_vertex: __sdk::TableUpdate<ChunkVertex>,
+    chunk_packed: __sdk::TableUpdate<ChunkPacked>,
+    chunk_prop: __sdk::TableUpdate<ChunkProp>,
+    chunk_water: __sdk::TableUpdate<ChunkWater>,
     material_definition: __sdk::TableUpdate<MaterialDefinition>,
     mesh: __sdk::TableUpdate<Mesh>,
+    player: __sdk::TableUpdate<StdbPlayer>,
 }
 
 impl TryFrom<__ws::DatabaseUpdate<__ws::BsatnFormat>> for DbUpdate {
@@ -114,17 +161,21 @@ impl TryFrom<__ws::DatabaseUpdate<__ws::BsatnFormat>> for DbUpdate {
         let mut db_update = DbUpdate::default();
         for table_update in raw.tables {
             match &table_update.table_name[..] {
-                "chunk_mesh" => {
-                    db_update.chunk_mesh = chunk_mesh_table::parse_table_update(table_update)?
+                "chunk_packed" => {
+                    db_update.chunk_packed = chunk_packed_table::parse_table_update(table_update)?
+                }
+                "chunk_prop" => {
+                    db_update.chunk_prop = chunk_prop_table::parse_table_update(table_update)?
                 }
-                "chunk_vertex" => {
-                    db_update.chunk_vertex = chunk_vertex_table::parse_table_update(table_update)?
+                "chunk_water" => {
+                    db_update.chunk_water = chunk_water_table::parse_table_update(table_update)?
                 }
                 "material_definition" => {
                     db_update.material_definition =
                         material_definition_table::parse_table_update(table_update)?
                 }
                 "mesh" => db_update.mesh = mesh_table::parse_table_update(table_update)?,
+                "player" => db_update.player = player_table::parse_table_update(table_update)?,
 
                 unknown => {
                     return Err(__sdk::InternalError::unknown_name(
@@ -151,12 +202,15 @@ impl __sdk::DbUpdate for DbUpdate {
     ) -> AppliedDiff<'_> {
         let mut diff = AppliedDiff::default();
 
-        diff.chunk_mesh = cache
-            .apply_diff_to_table::<ChunkMesh>("chunk_mesh", &self.chunk_mesh)
-            .with_updates_by_pk(|row| &row.grid);
-        diff.chunk_vertex = cache
-            .apply_diff_to_table::<ChunkVertex>("chunk_vertex", &self.chunk_vertex)
-            .with_updates_by_pk(|row| &row.grid);
+        diff.chunk_packed = cache
+            .apply_diff_to_table::<ChunkPacked>("chunk_packed", &self.chunk_packed)
+            .with_updates_by_pk(|row| &row.id);
+        diff.chunk_prop = cache
+            .apply_diff_to_table::<ChunkProp>("chunk_prop", &self.chunk_prop)
+            .with_updates_by_pk(|row| &row.id);
+        diff.chunk_water = cache
+            .apply_diff_to_table::<ChunkWater>("chunk_water", &self.chunk_water)
+            .with_updates_by_pk(|row| &row.key);
         diff.material_definition = cache
             .apply_diff_to_table::<MaterialDefinition>(
                 "material_definition",
@@ -166,6 +220,9 @@ impl __sdk::DbUpdate for DbUpdate {
         diff.mesh = cache
             .apply_diff_to_table::<Mesh>("mesh", &self.mesh)
             .with_updates_by_pk(|row| &row.id);
+        diff.player = cache
+            .apply_diff_to_table::<StdbPlayer>("player", &self.player)
+            .with_updates_by_pk(|row| &row.identity);
 
         diff
     }
@@ -175,10 +232,12 @@ impl __sdk::DbUpdate for DbUpdate {
 #[allow(non_snake_case)]
 #[doc(hidden)]
 pub struct AppliedDiff<'r> {
-    chunk_mesh: __sdk::TableAppliedDiff<'r, ChunkMesh>,
-    chunk_vertex: __sdk::TableAppliedDiff<'r, ChunkVertex>,
+    chunk_packed: __sdk::TableAppliedDiff<'r, ChunkPacked>,
+    chunk_prop: __sdk::TableAppliedDiff<'r, ChunkProp>,
+    chunk_water: __sdk::TableAppliedDiff<'r, ChunkWater>,
     material_definition: __sdk::TableAppliedDiff<'r, MaterialDefinition>,
     mesh: __sdk::TableAppliedDiff<'r, Mesh>,
+    player: __sdk::TableAppliedDiff<'r, StdbPlayer>,
 }
 
 impl __sdk::InModule for AppliedDiff<'_> {
@@ -191,18 +250,20 @@ impl<'r> __sdk::AppliedDiff<'r> for AppliedDiff<'r> {
         event: &EventContext,
         callbacks: &mut __sdk::DbCallbacks<RemoteModule>,
     ) {
-        callbacks.invoke_table_row_callbacks::<ChunkMesh>("chunk_mesh", &self.chunk_mesh, event);
-        callbacks.invoke_table_row_callbacks::<ChunkVertex>(
-            "chunk_vertex",
-            &self.chunk_vertex,
+        callbacks.invoke_table_row_callbacks::<ChunkPacked>(
+            "chunk_packed",
+            &self.chunk_packed,
             event,
         );
+        callbacks.invoke_table_row_callbacks::<ChunkProp>("chunk_prop", &self.chunk_prop, event);
+        callbacks.invoke_table_row_callbacks::<ChunkWater>("chunk_water", &self.chunk_water, event);
         callbacks.invoke_table_row_callbacks::<MaterialDefinition>(
             "material_definition",
             &self.material_definition,
             event,
         );
         callbacks.invoke_table_row_callbacks::<Mesh>("mesh", &self.mesh, event);
+        callbacks.invoke_table_row_callbacks::<StdbPlayer>("player", &self.player, event);
     }
 }
 
@@ -778,9 +839,11 @@ impl __sdk::SpacetimeModule for RemoteModule {
     type SubscriptionHandle = SubscriptionHandle;
 
     fn register_tables(client_cache: &mut __sdk::ClientCache<Self>) {
-        chunk_mesh_table::register_table(client_cache);
-        chunk_vertex_table::register_table(client_cache);
+        chunk_packed_table::register_table(client_cache);
+        chunk_prop_table::register_table(client_cache);
+        chunk_water_table::register_table(client_cache);
         material_definition_table::register_table(client_cache);
         mesh_table::register_table(client_cache);
+        player_table::register_table(client_cache);
     }
 }
@@ -0,0 +1,55 @@
+use std::collections::HashMap;
+use bevy::prelude::*;
+
+use crate::terrain::{
+    types::XZCoords,
+    dirtychunks::DirtyChunks,
+};
+
+/// Outer radius, in chunks, of each level of detail; level `n` is drawn out to `LOD_RADII[n]`.
+pub const LOD_RADII: [i32; 4] = [1, 2, 4, 8];
+/// Chunks further away than this are not drawn at all.
+pub const VIEW_RADIUS: i32 = LOD_RADII[LOD_RADII.len() - 1];
+
+/// Distance between chunks in rings around `center`.
+pub fn chunk_distance(a: &XZCoords, center: &XZCoords) -> i32 {
+    (a.x - center.x).abs().max((a.z - center.z).abs())
+}
+
+/// Level of detail a chunk should be drawn at, or `None` if it's out of view.
+pub fn lod_for(coords: &XZCoords, center: &XZCoords) -> Option<u8> {
+    let distance = chunk_distance(coords, center);
+    LOD_RADII.iter().position(|&radius| distance <= radius).map(|lod| lod as u8)
+}
+
+/// Spawned terrain entity for each drawn chunk, and the level it was drawn at.
+#[derive(Resource, Default)]
+pub struct ChunkEntities(pub HashMap<XZCoords, (Entity, u8)>);
+
+impl ChunkEntities {
+    /// After the view centre moves: despawns chunks that left the view and marks the ones
+    /// that need a different level (or haven't been drawn yet) dirty.
+    pub fn retarget(&mut self, center: &XZCoords, dirty_chunks: &mut DirtyChunks, commands: &mut Commands) {
+        self.0.retain(|coords, (entity, lod)| match lod_for(coords, center) {
+            Some(wanted) => {
+                if wanted != *lod {
+                    dirty_chunks.mark_dirty(coords.clone());
+                }
+                true
+            }
+            None => {
+                commands.entity(*entity).despawn();
+                false
+            }
+        });
+
+        for x in (center.x - VIEW_RADIUS)..=(center.x + VIEW_RADIUS) {
+            for z in (center.z - VIEW_RADIUS)..=(center.z + VIEW_RADIUS) {
+                let coords = XZCoords { x, z };
+                if !self.0.contains_key(&coords) {
+                    dirty_chunks.mark_dirty(coords);
+                }
+            }
+        }
+    }
+}
@@ -3,5 +3,9 @@ pub mod systems;
 pub mod types;
 pub mod ui;
 pub mod dirtychunks;
+pub mod lod;
+pub mod packed;
+pub mod water;
+pub mod props;
 
 pub use plugin::TerrainPlugin;
\ No newline at end of file
@@ -0,0 +1,86 @@
+use crate::terrain::types::ChunkPacked;
+
+/// Chunk side in height samples; must match the server's `CHUNK_SIZE`.
+const CHUNK_SIZE: usize = 32;
+
+/// Vertices per side of a level's grid.
+pub fn grid_size(lod: u8) -> usize {
+    CHUNK_SIZE / (1 << lod) + 1
+}
+
+/// Grid vertex indices along each edge, in the order the server hangs skirts: -z, +x, +z, -x.
+fn edges(lod: u8) -> [Vec<u32>; 4] {
+    let cells = grid_size(lod) - 1;
+    let index = |x: usize, z: usize| (z * (cells + 1) + x) as u32;
+    [
+        (0..=cells).map(|i| index(i, 0)).collect(),
+        (0..=cells).map(|i| index(cells, i)).collect(),
+        (0..=cells).rev().map(|i| index(i, cells)).collect(),
+        (0..=cells).rev().map(|i| index(0, i)).collect(),
+    ]
+}
+
+fn decode_octahedral(u: u8, v: u8) -> [f32; 3] {
+    let (mut u, mut v) = (u as f32 / 255.0 * 2.0 - 1.0, v as f32 / 255.0 * 2.0 - 1.0);
+    let y = 1.0 - u.abs() - v.abs();
+    if y < 0.0 {
+        (u, v) = ((1.0 - v.abs()) * u.signum(), (1.0 - u.abs()) * v.signum());
+    }
+    let length = (u * u + y * y + v * v).sqrt();
+    [u / length, y / length, v / length]
+}
+
+/// Height of grid vertex `(x, z)` of a packed chunk.
+pub fn height(chunk: &ChunkPacked, x: usize, z: usize) -> f32 {
+    chunk.height_min + chunk.heights[z * grid_size(chunk.lod) + x] as f32 * chunk.height_step
+}
+
+/// A packed chunk rebuilt into world-space positions, normals and triangle indices.
+pub struct UnpackedChunk {
+    pub positions: Vec<[f32; 3]>,
+    pub normals: Vec<[f32; 3]>,
+    pub indices: Vec<u32>,
+}
+
+/// Rebuilds the grid and skirts the server packed, in the same vertex and triangle order.
+pub fn unpack(chunk: &ChunkPacked) -> UnpackedChunk {
+    let grid = grid_size(chunk.lod);
+    let stride = 1 << chunk.lod;
+    let (origin_x, origin_z) = ((chunk.grid.x * CHUNK_SIZE as i32) as f32, (chunk.grid.z * CHUNK_SIZE as i32) as f32);
+
+    let mut positions = Vec::with_capacity(grid * grid);
+    let mut normals = Vec::with_capacity(grid * grid);
+    for z in 0..grid {
+        for x in 0..grid {
+            let i = z * grid + x;
+            positions.push([origin_x + (x * stride) as f32, height(chunk, x, z), origin_z + (z * stride) as f32]);
+            normals.push(decode_octahedral(chunk.normals[i * 2], chunk.normals[i * 2 + 1]));
+        }
+    }
+
+    let mut indices = Vec::with_capacity((grid - 1) * (grid - 1) * 6 + (grid - 1) * 4 * 6);
+    let index = |x: usize, z: usize| (z * grid + x) as u32;
+    for z in 0..grid - 1 {
+        for x in 0..grid - 1 {
+            let (a, b, c, d) = (index(x, z), index(x + 1, z), index(x + 1, z + 1), index(x, z + 1));
+            indices.extend_from_slice(&[a, c, b, a, d, c]);
+        }
+    }
+
+    // skirts: a lowered copy of each edge, joined to it with outward-facing quads
+    for (top, depth) in edges(chunk.lod).iter().zip(&chunk.skirt_depths) {
+        let base = positions.len() as u32;
+        for &t in top {
+            let [x, y, z] = positions[t as usize];
+            positions.push([x, y - depth, z]);
+            normals.push(normals[t as usize]);
+        }
+        for i in 0..top.len() - 1 {
+            let (t0, t1) = (top[i], top[i + 1]);
+            let (b0, b1) = (base + i as u32, base + i as u32 + 1);
+            indices.extend_from_slice(&[t0, t1, b0, t1, b1, b0]);
+        }
+    }
+
+    UnpackedChunk { positions, normals, indices }
+}
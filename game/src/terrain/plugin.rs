@@ -1,14 +1,17 @@
 use bevy::prelude::*;
-use bevy_spacetimedb::{InsertEvent, UpdateEvent};
+use bevy_spacetimedb::{InsertEvent, UpdateEvent, DeleteEvent};
 use crate::terrain::{
-    types::{ChunkVertex, ChunkMesh},
+    types::{ChunkPacked, ChunkWater, ChunkProp},
+    lod::ChunkEntities,
+    water::{WaterEntities, setup_water_material, on_water_insert, on_water_update, on_water_delete},
+    props::{PropEntities, setup_prop_assets, on_prop_insert, on_prop_delete},
     ui::setup_minimap_ui,
     types::{MinimapConfig, MinimapImage},
     dirtychunks::{DirtyChunks, dirtychunks_tick_system},
     systems::{
         TerrainSubscription, 
         terrain_subscription_system, 
-        on_chunk_insert, on_chunk_update, 
+        on_lod_insert, on_lod_update,
         render_terrain, setup_minimap_gradient
     },
 };
@@ -24,23 +27,34 @@ impl Plugin for TerrainPlugin {
         .insert_resource(minimap_config)
         .insert_resource(DirtyChunks::new(3))
         .init_resource::<TerrainSubscription>()
+        .init_resource::<ChunkEntities>()
+        .init_resource::<WaterEntities>()
+        .init_resource::<PropEntities>()
         .init_resource::<MinimapImage>()
 
-        .add_event::<InsertEvent<ChunkVertex>>()
-        .add_event::<UpdateEvent<ChunkVertex>>()
-        .add_event::<InsertEvent<ChunkMesh>>()
-        .add_event::<UpdateEvent<ChunkMesh>>()
+        .add_event::<InsertEvent<ChunkPacked>>()
+        .add_event::<UpdateEvent<ChunkPacked>>()
+        .add_event::<InsertEvent<ChunkWater>>()
+        .add_event::<UpdateEvent<ChunkWater>>()
+        .add_event::<DeleteEvent<ChunkWater>>()
+        .add_event::<InsertEvent<ChunkProp>>()
+        .add_event::<DeleteEvent<ChunkProp>>()
 
         // UI setup
-        .add_systems(Startup, (setup_minimap_ui, setup_minimap_gradient))
+        .add_systems(Startup, (setup_minimap_ui, setup_minimap_gradient, setup_water_material, setup_prop_assets))
 
         // terrain event handlers
         .add_systems(
             Update,
             (
                 terrain_subscription_system,
-                on_chunk_insert,
-                on_chunk_update,
+                on_lod_insert,
+                on_lod_update,
+                on_water_insert,
+                on_water_update,
+                on_water_delete,
+                on_prop_insert,
+                on_prop_delete,
                 render_terrain,
                 dirtychunks_tick_system,
                 // systems::update_minimap_arrow,
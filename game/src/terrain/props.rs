@@ -0,0 +1,91 @@
+use std::collections::HashMap;
+use bevy::prelude::*;
+use bevy_spacetimedb::{ReadInsertEvent, ReadDeleteEvent};
+
+use crate::terrain::{
+    types::ChunkProp,
+    lod::LOD_RADII,
+};
+
+/// Props are only subscribed out to this many chunks; further away they'd be a few pixels.
+pub const PROP_RADIUS: i32 = LOD_RADII[2];
+
+/// Prop kinds, as the server's `PropKind` ids.
+const PROP_TREE: u32 = 0;
+const PROP_BUSH: u32 = 1;
+const PROP_ROCK: u32 = 2;
+
+/// One mesh and material per prop kind. Every prop of a kind shares the same handles, so Bevy
+/// batches them into a single instanced draw.
+#[derive(Resource)]
+pub struct PropAssets(HashMap<u32, (Handle<Mesh>, Handle<StandardMaterial>)>);
+
+/// Spawned entity for each `chunk_prop` row, by row id.
+#[derive(Resource, Default)]
+pub struct PropEntities(pub HashMap<u64, Entity>);
+
+pub fn setup_prop_assets(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+) {
+    let mut material = |color: Color| materials.add(StandardMaterial {
+        base_color: color,
+        perceptual_roughness: 0.9,
+        ..default()
+    });
+    // meshes are built with their base at the origin, so props stand on the ground point
+    let assets = HashMap::from([
+        (PROP_TREE, (
+            meshes.add(Cone { radius: 1.2, height: 4.0 }.mesh().build().translated_by(Vec3::Y * 2.0)),
+            material(Color::srgb(0.13, 0.38, 0.15)),
+        )),
+        (PROP_BUSH, (
+            meshes.add(Sphere::new(0.7).mesh().ico(1).unwrap().translated_by(Vec3::Y * 0.4)),
+            material(Color::srgb(0.25, 0.45, 0.18)),
+        )),
+        (PROP_ROCK, (
+            meshes.add(Sphere::new(0.6).mesh().ico(0).unwrap().translated_by(Vec3::Y * 0.2)),
+            material(Color::srgb(0.48, 0.46, 0.43)),
+        )),
+    ]);
+    commands.insert_resource(PropAssets(assets));
+}
+
+pub fn on_prop_insert(
+    mut events: ReadInsertEvent<ChunkProp>,
+    assets: Res<PropAssets>,
+    mut prop_entities: ResMut<PropEntities>,
+    mut commands: Commands,
+) {
+    for event in events.read() {
+        let prop = &event.row;
+        let Some((mesh, material)) = assets.0.get(&prop.kind) else {
+            warn!("Unknown prop kind {}", prop.kind);
+            continue;
+        };
+        let entity = commands.spawn((
+            Mesh3d(mesh.clone()),
+            MeshMaterial3d(material.clone()),
+            Transform::from_xyz(prop.x, prop.y, prop.z)
+                .with_rotation(Quat::from_rotation_y(prop.rotation))
+                .with_scale(Vec3::splat(prop.scale)),
+        )).id();
+        if let Some(old) = prop_entities.0.insert(prop.id, entity) {
+            commands.entity(old).despawn();
+        }
+    }
+}
+
+/// Rows leave the cache when their chunk is rebuilt or falls out of the subscribed radius.
+pub fn on_prop_delete(
+    mut events: ReadDeleteEvent<ChunkProp>,
+    mut prop_entities: ResMut<PropEntities>,
+    mut commands: Commands,
+) {
+    for event in events.read() {
+        if let Some(entity) = prop_entities.0.remove(&event.row.id) {
+            commands.entity(entity).despawn();
+        }
+    }
+}
@@ -14,10 +14,10 @@ use spacetimedb_sdk::{
 };
 
 use crate::stdb::{
-    SubscriptionHandle, DbConnection,
-    on_chunk_requested,
-    chunk_vertex_table::ChunkVertexTableAccess,
-    chunk_mesh_table::ChunkMeshTableAccess,
+    SubscriptionHandle, DbConnection, RemoteTables,
+    request_chunk_region,
+    on_view_moved,
+    chunk_packed_table::ChunkPackedTableAccess,
     Mesh as TerrainMesh,
 };
 
@@ -26,10 +26,14 @@ use colorgrad::{CustomGradient, Gradient};
 use crate::terrain::{
     types::{
         XZCoords,
-        ChunkVertex, ChunkMesh, 
-        MinimapUi, MinimapConfig
+        ChunkPacked,
+        MinimapUi, MinimapConfig,
+        PackedIndex,
     },
     dirtychunks::DirtyChunks,
+    lod::{ChunkEntities, LOD_RADII, VIEW_RADIUS, lod_for},
+    packed,
+    props::PROP_RADIUS,
 };
 
 #[derive(Resource)]
@@ -40,12 +44,15 @@ const HEIGHT_RANGE: f32 = 32.0;
 /// Radius in chunks for subscribing
 const SUB_RADIUS: i32 = 3;
 const CHUNK_SIZE: i32 = 32;
+/// Side of the square tiles the view is requested in; the server caps a region at 81 chunks.
+const REGION_TILE: i32 = 9;
 
 /// Holds the current heightmap subscription handle
 #[derive(Resource, Default)]
 pub struct TerrainSubscription {
-    vertex_handle: Option<SubscriptionHandle>,
-    mesh_handle: Option<SubscriptionHandle>,
+    lod_handles: Vec<SubscriptionHandle>,
+    water_handle: Option<SubscriptionHandle>,
+    prop_handle: Option<SubscriptionHandle>,
     last_center: Option<XZCoords>,
 }
 
@@ -57,6 +64,8 @@ pub fn terrain_subscription_system(
     minimap_config: Res<MinimapConfig>,
     mut sub: ResMut<TerrainSubscription>,
     mut dirty_chunks: ResMut<DirtyChunks>,
+    mut chunk_entities: ResMut<ChunkEntities>,
+    mut commands: Commands,
 ) {
     // if connected or moved across chunk boundary
     let reconnect = c_evt.read().next().is_some();
@@ -77,69 +86,155 @@ pub fn terrain_subscription_system(
             // as per spacetime docs, we should subscribe before unsubscribing
             // "This is because SpacetimeDB subscriptions are zero-copy. Subscribing to the same query more than once doesn't incur additional processing or serialization overhead."
             dirty_chunks.populate_radius(center.clone());
+            chunk_entities.retarget(&center, &mut dirty_chunks, &mut commands);
+            match sub.last_center.clone().filter(|_| !reconnect) {
+                Some(from) => {
+                    // the rest of the view was requested before; the server pre-generates
+                    // ahead of where we're heading
+                    request_exposed(&stdb, &from, &center);
+                    let _ = stdb.conn().reducers.on_view_moved(from, center.clone());
+                }
+                None => request_view(&stdb, &center),
+            }
 
-            // compute bounds
-            let minimap_radius = 3; //minimap_config.radius as i32;
-            let (min_x, max_x) = (cx - minimap_radius, cx + minimap_radius);
-            let (min_z, max_z) = (cz - minimap_radius, cz + minimap_radius);
-            let predicate = format!(
-                "WHERE chunk_vertex.grid_x >= {} AND chunk_vertex.grid_x <= {} AND chunk_vertex.grid_z >= {} AND chunk_vertex.grid_z <= {}",
-                min_x, max_x, min_z, max_z
-            );
-            // subscribe
-            let vertex_handle = stdb
-                .subscribe()
-                .on_applied(|ctx| {
-                    info!("Subscribed to terrain: {} chunks", ctx.db.chunk_vertex().count());
-                })
-                .on_error(|_, e| error!("Terrain sub error: {}", e))
-                .subscribe(format!("SELECT * FROM chunk_vertex {}", predicate));
-
-            let predicate = format!(
-                "WHERE chunk_mesh.grid_x >= {} AND chunk_mesh.grid_x <= {} AND chunk_mesh.grid_z >= {} AND chunk_mesh.grid_z <= {}",
-                min_x, max_x, min_z, max_z
-            );
-            let mesh_handle = stdb
-                .subscribe()
-                .on_applied(|ctx| {
-                    info!("Subscribed to terrain meshes: {}", ctx.db.chunk_mesh().count());
+            // one subscription per level of detail, each covering its own ring and everything inside it;
+            // the packed rows carry the minimap heights too, so nothing else is subscribed
+            let lod_handles: Vec<SubscriptionHandle> = LOD_RADII
+                .iter()
+                .enumerate()
+                .map(|(lod, &radius)| {
+                    stdb.subscribe()
+                        .on_applied(move |ctx| {
+                            info!("Subscribed to terrain LOD {}: {} chunks", lod, ctx.db.chunk_packed().count());
+                        })
+                        .on_error(|_, e| error!("Terrain LOD sub error: {}", e))
+                        .subscribe(format!(
+                            "SELECT * FROM chunk_packed WHERE chunk_packed.lod = {} AND chunk_packed.grid_x >= {} AND chunk_packed.grid_x <= {} AND chunk_packed.grid_z >= {} AND chunk_packed.grid_z <= {}",
+                            lod, cx - radius, cx + radius, cz - radius, cz + radius
+                        ))
                 })
-                .on_error(|_, e| error!("Terrain sub error: {}", e))
-                .subscribe(format!("SELECT * FROM chunk_mesh {}", predicate));
+                .collect();
 
-            
-            if let Some(h) = sub.vertex_handle.take() {
+            // water is small, so one subscription covers the whole view
+            let water_handle = stdb.subscribe()
+                .on_error(|_, e| error!("Water sub error: {}", e))
+                .subscribe(format!(
+                    "SELECT * FROM chunk_water WHERE chunk_water.grid_x >= {} AND chunk_water.grid_x <= {} AND chunk_water.grid_z >= {} AND chunk_water.grid_z <= {}",
+                    cx - VIEW_RADIUS, cx + VIEW_RADIUS, cz - VIEW_RADIUS, cz + VIEW_RADIUS
+                ));
+
+            let prop_handle = stdb.subscribe()
+                .on_error(|_, e| error!("Prop sub error: {}", e))
+                .subscribe(format!(
+                    "SELECT * FROM chunk_prop WHERE chunk_prop.grid_x >= {} AND chunk_prop.grid_x <= {} AND chunk_prop.grid_z >= {} AND chunk_prop.grid_z <= {}",
+                    cx - PROP_RADIUS, cx + PROP_RADIUS, cz - PROP_RADIUS, cz + PROP_RADIUS
+                ));
+
+            if let Some(h) = sub.prop_handle.take() {
                 let _ = h.unsubscribe();
             }
 
-            if let Some(h) = sub.mesh_handle.take() {
+            if let Some(h) = sub.water_handle.take() {
+                let _ = h.unsubscribe();
+            }
+
+            for h in sub.lod_handles.drain(..) {
                 let _ = h.unsubscribe();
             }
 
             // store state
-            sub.vertex_handle = Some(vertex_handle);
-            sub.mesh_handle = Some(mesh_handle);
+            sub.lod_handles = lod_handles;
+            sub.water_handle = Some(water_handle);
+            sub.prop_handle = Some(prop_handle);
             sub.last_center = Some(center);
         }
     }
 }
 
-pub fn on_chunk_insert(
-    mut events: ReadInsertEvent<ChunkVertex>,
+/// Asks the server to generate every chunk in view of `center`, the subscribed radius first
+/// so the chunks around the camera arrive before the far rings.
+fn request_view(stdb: &StdbConnection<DbConnection>, center: &XZCoords) {
+    request_region(stdb, &square(center, SUB_RADIUS));
+    request_region(stdb, &square(center, VIEW_RADIUS));
+}
+
+/// Asks the server for only the chunks a move from `from` to `to` brings into view, again
+/// the subscribed radius first.
+fn request_exposed(stdb: &StdbConnection<DbConnection>, from: &XZCoords, to: &XZCoords) {
+    for radius in [SUB_RADIUS, VIEW_RADIUS] {
+        for region in exposed(from, to, radius) {
+            request_region(stdb, &region);
+        }
+    }
+}
+
+/// The inclusive square of chunks within `radius` of `center`.
+fn square(center: &XZCoords, radius: i32) -> (XZCoords, XZCoords) {
+    (
+        XZCoords { x: center.x - radius, z: center.z - radius },
+        XZCoords { x: center.x + radius, z: center.z + radius },
+    )
+}
+
+/// Rectangles covering the square around `to` that the square around `from` doesn't: a strip
+/// along x for the x move, and one along z over the columns both squares share.
+fn exposed(from: &XZCoords, to: &XZCoords, radius: i32) -> Vec<(XZCoords, XZCoords)> {
+    let (dx, dz) = (to.x - from.x, to.z - from.z);
+    if dx.abs() > 2 * radius || dz.abs() > 2 * radius {
+        return vec![square(to, radius)];
+    }
+
+    let mut regions = Vec::new();
+    if dx != 0 {
+        let (min_x, max_x) = if dx > 0 { (from.x + radius + 1, to.x + radius) } else { (to.x - radius, from.x - radius - 1) };
+        regions.push((XZCoords { x: min_x, z: to.z - radius }, XZCoords { x: max_x, z: to.z + radius }));
+    }
+    if dz != 0 {
+        let (min_z, max_z) = if dz > 0 { (from.z + radius + 1, to.z + radius) } else { (to.z - radius, from.z - radius - 1) };
+        let (min_x, max_x) = (to.x.max(from.x) - radius, to.x.min(from.x) + radius);
+        regions.push((XZCoords { x: min_x, z: min_z }, XZCoords { x: max_x, z: max_z }));
+    }
+    regions
+}
+
+/// Requests an inclusive rectangle of chunks in tiles small enough for the server's cap.
+fn request_region(stdb: &StdbConnection<DbConnection>, (min, max): &(XZCoords, XZCoords)) {
+    for tz in (min.z..=max.z).step_by(REGION_TILE as usize) {
+        for tx in (min.x..=max.x).step_by(REGION_TILE as usize) {
+            let tile_min = XZCoords { x: tx, z: tz };
+            let tile_max = XZCoords { x: (tx + REGION_TILE - 1).min(max.x), z: (tz + REGION_TILE - 1).min(max.z) };
+            if let Err(e) = stdb.conn().reducers.request_chunk_region(tile_min, tile_max) {
+                error!("Failed to request chunks: {}", e);
+            }
+        }
+    }
+}
+
+/// Keeps a `PackedIndex` in step with the client cache. Called while the connection is built,
+/// before any terrain is subscribed.
+pub fn register_packed_index(app: &mut App, db: &RemoteTables) {
+    let index = PackedIndex::default();
+    let inserted = index.clone();
+    db.chunk_packed().on_insert(move |_ctx, row| inserted.insert(row));
+    let deleted = index.clone();
+    db.chunk_packed().on_delete(move |_ctx, row| deleted.remove(row));
+    app.insert_resource(index);
+}
+
+pub fn on_lod_insert(
+    mut events: ReadInsertEvent<ChunkPacked>,
     mut dirty_chunks: ResMut<DirtyChunks>,
 ) {
     for event in events.read() {
-        info!("Chunk inserted: {:?}   Marking dirty", event.row.grid);
         dirty_chunks.mark_dirty(event.row.grid.clone());
     }
 }
 
-pub fn on_chunk_update(
-    mut events: ReadUpdateEvent<ChunkVertex>,
+pub fn on_lod_update(
+    mut events: ReadUpdateEvent<ChunkPacked>,
     mut dirty_chunks: ResMut<DirtyChunks>,
 ) {
     for event in events.read() {
-        info!("Chunk updated: {:?}   Marking dirty", event.new.grid);
         dirty_chunks.mark_dirty(event.new.grid.clone());
     }
 }
@@ -166,7 +261,9 @@ pub fn render_terrain(
     minimap_config: Res<MinimapConfig>,
     gradient_res: Res<TerrainGradient>,
     stdb: Res<StdbConnection<DbConnection>>,
+    packed_index: Res<PackedIndex>,
     mut dirty_chunks: ResMut<DirtyChunks>,
+    mut chunk_entities: ResMut<ChunkEntities>,
     mut meshes: ResMut<Assets<Mesh>>,
     mut materials: ResMut<Assets<StandardMaterial>>,
     mut images: ResMut<Assets<Image>>,
@@ -189,66 +286,61 @@ pub fn render_terrain(
     // Determine center chunk
     let center = sub.last_center.clone().unwrap_or(XZCoords { x: 0, z: 0 });
 
-    let mesh_table = stdb.db().chunk_mesh();
-    let vertex_table = stdb.db().chunk_vertex();
+    let packed_table = stdb.db().chunk_packed();
 
     while let Some(coords) = dirty_chunks.pop_dirty() {
-        if let Some(chunk_vertex) = vertex_table.iter().find(|row| row.grid == coords) {
-            info!("Chunk found: {:?}", coords);
-            let heightmap = chunk_vertex.heightmap.clone();
-            match mesh_table.iter().find(|row| row.grid == coords) {
-                Some(chunk_mesh) => {
-                    render_chunk(&mut commands, &mut meshes, &mut materials, chunk_vertex, chunk_mesh, coords.clone());
-                }
-                None => {
-                    dirty_chunks.schedule_retry(coords.clone(), 1.0);
-                    info!("No mesh found for chunk: {:?}", coords);
+        // draw the chunk at the level its distance calls for
+        let Some(lod) = lod_for(&coords, &center) else {
+            continue;
+        };
+        let Some(chunk) = packed_index.get(&coords, lod).and_then(|id| packed_table.id().find(&id)) else {
+            // requested with the rest of the view; retry until it arrives
+            dirty_chunks.schedule_retry(coords.clone(), 1.0);
+            continue;
+        };
+
+        // Generate RGBA bytes functionally, then write each row in one go.
+        // Each pixel takes the nearest grid vertex, so every level paints a full chunk.
+        let stride = 1usize << chunk.lod;
+        let pixel_bytes: Vec<u8> = (0..chunk_size as usize)
+            .flat_map(|z| (0..chunk_size as usize).map(move |x| (x, z)))
+            .flat_map(|(x, z)| {
+                let height = packed::height(&chunk, (x + stride / 2) / stride, (z + stride / 2) / stride);
+                let normalized = ((height+HEIGHT_RANGE) / HEIGHT_RANGE).clamp(0.0, 1.0) as f64;
+                let color = gradient_res.0.at(normalized);
+                // Expand into an array, which IntoIterator flattens
+                [
+                    (color.r * 255.0) as u8,
+                    (color.g * 255.0) as u8,
+                    (color.b * 255.0) as u8,
+                    255u8,
+                ]
+            })
+            .collect();
+
+        // Calculate the offset to center the chunk on the texture
+        let offset_x = ((coords.x - center.x) * chunk_size as i32) + (tex_size as i32 - chunk_size as i32) / 2;
+        let offset_z = ((coords.z - center.z) * chunk_size as i32) + (tex_size as i32 - chunk_size as i32) / 2;
+
+        // Copy each row slice into the image buffer with consistent usize indexing
+        let row_stride = (chunk_size * 4) as usize;
+        pixel_bytes
+            .chunks(row_stride)
+            .enumerate()
+            .for_each(|(row_z, row_bytes)| {
+                let dest_z = (offset_z + row_z as i32) as usize;
+                let dest_x = (offset_x)  as usize;
+                if dest_z < tex_size as usize && dest_x < tex_size as usize {
+                    let dest = (dest_z * tex_size as usize + dest_x) * 4;
+                    data[dest..dest + row_bytes.len()].copy_from_slice(row_bytes);
+                } else {
+                    info!("Skipping copy of row {}: ({},{})", row_z, dest_x, dest_z);
                 }
-            }
-
-            // Generate RGBA bytes functionally, then write each row in one go.
-            let pixel_bytes: Vec<u8> = heightmap
-                .iter()
-                .flat_map(|&height| {
-                    let normalized = ((height+HEIGHT_RANGE) / HEIGHT_RANGE).clamp(0.0, 1.0) as f64;
-                    let color = gradient_res.0.at(normalized);
-                    // Expand into an array, which IntoIterator flattens
-                    [
-                        (color.r * 255.0) as u8,
-                        (color.g * 255.0) as u8,
-                        (color.b * 255.0) as u8,
-                        255u8,
-                    ]
-                })
-                .collect();
-
-            // Calculate the offset to center the chunk on the texture
-            let offset_x = ((coords.x - center.x) * chunk_size as i32) + (tex_size as i32 - chunk_size as i32) / 2;
-            let offset_z = ((coords.z - center.z) * chunk_size as i32) + (tex_size as i32 - chunk_size as i32) / 2;
+            });
 
-            // info!("Offset x/z: {}, {}", offset_x, offset_z);
-
-            // Copy each row slice into the image buffer with consistent usize indexing
-            let row_stride = (chunk_size * 4) as usize;
-            pixel_bytes
-                .chunks(row_stride)
-                .enumerate()
-                .for_each(|(row_z, row_bytes)| {
-                    let dest_z = (offset_z + row_z as i32) as usize;
-                    let dest_x = (offset_x)  as usize;
-                    if dest_z < tex_size as usize && dest_x < tex_size as usize {
-                        let dest = (dest_z * tex_size as usize + dest_x) * 4;
-                        // info!("Copying row {} to dest: ({},{}) {}", row_z, dest_x, dest_z, dest);
-                        data[dest..dest + row_bytes.len()].copy_from_slice(row_bytes);
-                    } else {
-                        info!("Skipping copy of row {}: ({},{})", row_z, dest_x, dest_z);
-                    }
-                });
-        } else {
-            // No chunk yet for these coordinates, retry later
-            let _ = stdb.conn().reducers.on_chunk_requested(coords.clone());
-            dirty_chunks.schedule_retry(coords.clone(), 1.0);
-            info!("No chunk found for coords: {:?}", coords);
+        let entity = render_chunk(&mut commands, &mut meshes, &mut materials, &chunk, coords.clone());
+        if let Some((old, _)) = chunk_entities.0.insert(coords.clone(), (entity, lod)) {
+            commands.entity(old).despawn();
         }
     }
 }
@@ -257,28 +349,20 @@ fn render_chunk(
     commands: &mut Commands,
     meshes: &mut ResMut<Assets<Mesh>>,
     materials: &mut ResMut<Assets<StandardMaterial>>,
-    chunk_vertex: ChunkVertex,
-    chunk_mesh: ChunkMesh,
+    chunk: &ChunkPacked,
     coords: XZCoords,
-) {
-    info!("Mesh found: {:?}", chunk_mesh.grid);
+) -> Entity {
+    info!("Mesh found: {:?} LOD {}", chunk.grid, chunk.lod);
     let mut mesh = Mesh::new(PrimitiveTopology::TriangleList, RenderAssetUsages::MAIN_WORLD | RenderAssetUsages::RENDER_WORLD);
-    let positions = chunk_vertex.vertices
-        .chunks_exact(3)
-        .map(|c| [c[0], c[1], c[2]])
-        .collect::<Vec<_>>();
-    
-    mesh.insert_attribute(Mesh::ATTRIBUTE_POSITION, positions);
-
-    let normals = chunk_vertex.normals
-        .chunks_exact(3)
-        .map(|c| [c[0], c[1], c[2]])
-        .collect::<Vec<_>>();
-    mesh.insert_attribute(Mesh::ATTRIBUTE_NORMAL, normals);
+    // positions and indices are implied by the grid; only heights, normals and skirts come over the wire
+    let unpacked = packed::unpack(chunk);
+
+    mesh.insert_attribute(Mesh::ATTRIBUTE_POSITION, unpacked.positions);
+    mesh.insert_attribute(Mesh::ATTRIBUTE_NORMAL, unpacked.normals);
 
     // mesh.insert_attribute(Mesh::ATTRIBUTE_UV_0, vec![[0.0, 0.0]; pos_len]);
 
-    mesh.insert_indices(Indices::U32(chunk_mesh.indices));
+    mesh.insert_indices(Indices::U32(unpacked.indices));
 
 
     let mesh_handle = meshes.add(mesh);
@@ -314,7 +398,7 @@ fn render_chunk(
         // transform, // Use calculated transform
         Transform::IDENTITY,
         Name::new(format!("TerrainChunk_{}_{}", coords.x, coords.z)),
-    ));
+    )).id()
 }
 
 pub fn setup_minimap_gradient(mut commands: Commands) {
@@ -1,3 +1,6 @@
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
 use bevy::prelude::*;
 
 #[derive(Resource, Copy, Clone)]
@@ -24,7 +27,26 @@ impl Default for MinimapConfig {
 pub struct MinimapImage(pub Handle<Image>);
 
 // reuse your HeightmapChunk and ChunkCoords from the generated stdb module
-pub use crate::stdb::{ChunkVertex, ChunkMesh, xz_coords_type::XzCoords as XZCoords};
+pub use crate::stdb::{ChunkPacked, ChunkWater, ChunkProp, xz_coords_type::XzCoords as XZCoords};
 
 #[derive(Component)]
-pub struct MinimapUi(pub Handle<Image>);
\ No newline at end of file
+pub struct MinimapUi(pub Handle<Image>);
+
+/// Row ids of the cached `chunk_packed` rows by chunk and level, so a chunk's mesh is found
+/// through the table's `id` index instead of a scan. Kept up to date by table callbacks.
+#[derive(Resource, Clone, Default)]
+pub struct PackedIndex(pub Arc<Mutex<HashMap<(i32, i32, u8), u64>>>);
+
+impl PackedIndex {
+    pub fn get(&self, coords: &XZCoords, lod: u8) -> Option<u64> {
+        self.0.lock().unwrap().get(&(coords.x, coords.z, lod)).copied()
+    }
+
+    pub fn insert(&self, row: &ChunkPacked) {
+        self.0.lock().unwrap().insert((row.grid.x, row.grid.z, row.lod), row.id);
+    }
+
+    pub fn remove(&self, row: &ChunkPacked) {
+        self.0.lock().unwrap().remove(&(row.grid.x, row.grid.z, row.lod));
+    }
+}
\ No newline at end of file
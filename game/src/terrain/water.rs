@@ -0,0 +1,118 @@
+use std::collections::HashMap;
+use bevy::prelude::*;
+use bevy::render::mesh::{PrimitiveTopology, Mesh, Indices};
+use bevy::render::render_asset::RenderAssetUsages;
+use bevy_spacetimedb::{ReadInsertEvent, ReadUpdateEvent, ReadDeleteEvent};
+
+use crate::terrain::types::{ChunkWater, XZCoords};
+
+/// Chunk side in cells; must match the server's `CHUNK_SIZE`.
+const CHUNK_SIZE: usize = 32;
+
+/// Spawned water surface for each chunk that has one.
+#[derive(Resource, Default)]
+pub struct WaterEntities(pub HashMap<XZCoords, Entity>);
+
+/// Translucent material shared by every water surface.
+#[derive(Resource)]
+pub struct WaterMaterial(pub Handle<StandardMaterial>);
+
+pub fn setup_water_material(mut commands: Commands, mut materials: ResMut<Assets<StandardMaterial>>) {
+    let material = materials.add(StandardMaterial {
+        base_color: Color::srgba(0.10, 0.35, 0.60, 0.6),
+        alpha_mode: AlphaMode::Blend,
+        perceptual_roughness: 0.1,
+        reflectance: 0.3,
+        // seen from below when the camera dips under the surface
+        double_sided: true,
+        cull_mode: None,
+        ..default()
+    });
+    commands.insert_resource(WaterMaterial(material));
+}
+
+fn is_wet_cell(mask: &[u8], x: usize, z: usize) -> bool {
+    let cell = z * CHUNK_SIZE + x;
+    mask.get(cell / 8).is_some_and(|byte| byte & (1 << (cell % 8)) != 0)
+}
+
+/// Flat quads at sea level over every wet cell of the chunk's water mask.
+fn water_mesh(water: &ChunkWater) -> Mesh {
+    let (origin_x, origin_z) = ((water.grid.x * CHUNK_SIZE as i32) as f32, (water.grid.z * CHUNK_SIZE as i32) as f32);
+    let mut positions = Vec::new();
+    let mut indices = Vec::new();
+    for z in 0..CHUNK_SIZE {
+        for x in 0..CHUNK_SIZE {
+            if !is_wet_cell(&water.mask, x, z) {
+                continue;
+            }
+            let base = positions.len() as u32;
+            for (dx, dz) in [(0, 0), (1, 0), (1, 1), (0, 1)] {
+                positions.push([origin_x + (x + dx) as f32, water.sea_level, origin_z + (z + dz) as f32]);
+            }
+            let (a, b, c, d) = (base, base + 1, base + 2, base + 3);
+            indices.extend_from_slice(&[a, c, b, a, d, c]);
+        }
+    }
+
+    let mut mesh = Mesh::new(PrimitiveTopology::TriangleList, RenderAssetUsages::MAIN_WORLD | RenderAssetUsages::RENDER_WORLD);
+    mesh.insert_attribute(Mesh::ATTRIBUTE_NORMAL, vec![[0.0, 1.0, 0.0]; positions.len()]);
+    mesh.insert_attribute(Mesh::ATTRIBUTE_POSITION, positions);
+    mesh.insert_indices(Indices::U32(indices));
+    mesh
+}
+
+fn spawn_water(
+    water: &ChunkWater,
+    water_entities: &mut WaterEntities,
+    material: &WaterMaterial,
+    meshes: &mut Assets<Mesh>,
+    commands: &mut Commands,
+) {
+    let entity = commands.spawn((
+        Mesh3d(meshes.add(water_mesh(water))),
+        MeshMaterial3d(material.0.clone()),
+        Transform::IDENTITY,
+        Name::new(format!("Water_{}_{}", water.grid.x, water.grid.z)),
+    )).id();
+    if let Some(old) = water_entities.0.insert(water.grid.clone(), entity) {
+        commands.entity(old).despawn();
+    }
+}
+
+pub fn on_water_insert(
+    mut events: ReadInsertEvent<ChunkWater>,
+    mut water_entities: ResMut<WaterEntities>,
+    material: Res<WaterMaterial>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut commands: Commands,
+) {
+    for event in events.read() {
+        spawn_water(&event.row, &mut water_entities, &material, &mut meshes, &mut commands);
+    }
+}
+
+pub fn on_water_update(
+    mut events: ReadUpdateEvent<ChunkWater>,
+    mut water_entities: ResMut<WaterEntities>,
+    material: Res<WaterMaterial>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut commands: Commands,
+) {
+    for event in events.read() {
+        spawn_water(&event.new, &mut water_entities, &material, &mut meshes, &mut commands);
+    }
+}
+
+/// Rows leave the cache when a chunk dries up or falls out of the subscribed view.
+pub fn on_water_delete(
+    mut events: ReadDeleteEvent<ChunkWater>,
+    mut water_entities: ResMut<WaterEntities>,
+    mut commands: Commands,
+) {
+    for event in events.read() {
+        if let Some(entity) = water_entities.0.remove(&event.row.grid) {
+            commands.entity(entity).despawn();
+        }
+    }
+}
@@ -0,0 +1,82 @@
+// benches/chunk_lookup.rs
+//
+// Times `chunk_is_current` against 100, 1k and 10k stored chunks held in btrees keyed like
+// the chunk tables' primary key and `idx_grid_lod`, next to a scan of every stored chunk like
+// the one `on_chunk_requested` used to make. Indexed lookups only grow with the depth of the
+// btree, while the scan grows with the world. Run with `cargo bench --bench chunk_lookup`.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::hint::black_box;
+use std::time::Instant;
+
+use realm_backend::terrain::chunk::{chunk_is_current, StoredChunks};
+use realm_backend::terrain::XZCoords;
+
+const LOOKUPS: i32 = 1_000_000;
+const SCANS: i32 = 1_000;
+const GENERATOR_VERSION: u32 = 1;
+
+struct Indexes {
+    vertex: BTreeMap<(i32, i32), u32>,
+    meshes: BTreeSet<(i32, i32, u8)>,
+}
+
+impl Indexes {
+    /// A square world of `side * side` current chunks.
+    fn square(side: i32) -> Self {
+        let mut indexes = Indexes { vertex: BTreeMap::new(), meshes: BTreeSet::new() };
+        for z in 0..side {
+            for x in 0..side {
+                indexes.vertex.insert((x, z), GENERATOR_VERSION);
+                indexes.meshes.insert((x, z, 0));
+            }
+        }
+        indexes
+    }
+}
+
+/// The chunk's stored version found by visiting every stored chunk.
+fn scan(indexes: &Indexes, coord: XZCoords) -> Option<u32> {
+    indexes.vertex.iter().find(|(&grid, _)| grid == (coord.x, coord.z)).map(|(_, &version)| version)
+}
+
+impl StoredChunks for Indexes {
+    fn chunk_version(&self, coord: XZCoords) -> Option<u32> {
+        self.vertex.get(&(coord.x, coord.z)).copied()
+    }
+
+    fn has_meshes(&self, coord: XZCoords, lod: u8) -> bool {
+        self.meshes.contains(&(coord.x, coord.z, lod))
+    }
+}
+
+fn main() {
+    for side in [10, 32, 100] {
+        let indexes = Indexes::square(side);
+        let start = Instant::now();
+        let mut current = 0;
+        for i in 0..LOOKUPS {
+            // one lookup in `side + 1` lands just past the edge of the world and misses
+            let coord = XZCoords { x: i % (side + 1), z: (i / side) % side };
+            if chunk_is_current(&indexes, black_box(coord), GENERATOR_VERSION) {
+                current += 1;
+            }
+        }
+        let indexed = start.elapsed().as_nanos() as f64 / LOOKUPS as f64;
+
+        let start = Instant::now();
+        for i in 0..SCANS {
+            let coord = XZCoords { x: i % (side + 1), z: (i / side) % side };
+            black_box(scan(&indexes, black_box(coord)));
+        }
+        let scanned = start.elapsed().as_nanos() as f64 / SCANS as f64;
+
+        println!(
+            "{:>6} stored chunks: {:>8.1} ns per indexed lookup, {:>10.1} ns per scan ({} current)",
+            side * side,
+            indexed,
+            scanned,
+            black_box(current),
+        );
+    }
+}
@@ -1,3 +1,7 @@
+pub mod transform;
 pub mod mesh;
+pub mod player;
 
-pub use mesh::Mesh;
\ No newline at end of file
+pub use transform::{StdbPosition, StdbRotation, StdbTransform};
+pub use mesh::Mesh;
+pub use player::StdbPlayer;
\ No newline at end of file
@@ -1,32 +1,127 @@
 // src/entity/player.rs
 
+use nalgebra::Vector3;
+use spacetimedb::{reducer, table, Identity, ReducerContext, Table, Timestamp};
 
+use crate::entity::transform::{StdbPosition, StdbRotation, StdbTransform};
+use crate::terrain::query::{height_at, TerrainQuery};
+
+/// How far above the ground new players appear.
+const SPAWN_HEIGHT: f32 = 2.0;
+/// Fastest a player may move, in blocks per second. Mirrors the client's `PlayerSettings::speed`.
+pub const MAX_SPEED: f32 = 12.0;
+/// Allowance over `MAX_SPEED` for uneven frame times and network jitter.
+const SPEED_TOLERANCE: f32 = 1.25;
+/// How far a client's clock may run ahead of the server's between two updates, in seconds.
+const CLOCK_SLACK: f32 = 0.25;
+/// Longest gap between updates that counts towards the distance allowed, in seconds, so a
+/// quiet client can't bank up a long jump.
+const MAX_CREDITED_GAP: f32 = 1.0;
+/// Moves longer than this are rejected outright rather than clamped.
+const TELEPORT_DISTANCE: f32 = 32.0;
+/// Closest a player may get to the ground.
+const GROUND_CLEARANCE: f32 = 0.5;
+/// Farthest a player may go from the origin along either horizontal axis.
+pub const WORLD_EXTENT: f32 = 1_000_000.0;
+/// Highest a player may fly.
+pub const WORLD_CEILING: f32 = 4096.0;
+
+/// One row per identity that has ever connected. Rows outlive their connection, so a player
+/// comes back where they left off.
+#[table(name = player, public)]
+#[derive(Clone, Debug)]
 pub struct StdbPlayer {
     #[primary_key]
-    pub id: u64,
+    pub identity: Identity,
     pub name: String,
     pub transform: StdbTransform,
+    pub online: bool,
+    /// Server time the transform was last accepted.
+    pub last_update: Timestamp,
+    /// Client clock sent with that update; older updates are dropped.
+    pub last_client_time: Timestamp,
 }
 
-#[reducer]
-pub fn on_player_created(ctx: &ReducerContext, player: StdbPlayer) -> Result<(), String> {
-    ctx.db.player().insert(player);
-    Ok(())
-}
+/// Creates the caller's player on their first connection and brings it back online after that.
+pub fn connect_player(ctx: &ReducerContext) {
+    let players = ctx.db.player();
+    if let Some(player) = players.identity().find(ctx.sender) {
+        // a new session may come with a different clock
+        players.identity().update(StdbPlayer {
+            online: true,
+            last_update: ctx.timestamp,
+            last_client_time: Timestamp::UNIX_EPOCH,
+            ..player
+        });
+        return;
+    }
 
-#[reducer]
-pub fn on_player_updated(ctx: &ReducerContext, player: StdbPlayer) -> Result<(), String> {
-    ctx.db.player().update(player);
-    Ok(())
+    // spawn is queued for generation at init, so this reads the stored chunk once it exists
+    let spawn = StdbPosition { x: 0.0, y: height_at(ctx, 0.0, 0.0) + SPAWN_HEIGHT, z: 0.0 };
+    players.insert(StdbPlayer {
+        identity: ctx.sender,
+        name: format!("Player {}", ctx.sender.to_abbreviated_hex()),
+        transform: StdbTransform { position: spawn, rotation: StdbRotation::IDENTITY },
+        online: true,
+        last_update: ctx.timestamp,
+        last_client_time: Timestamp::UNIX_EPOCH,
+    });
 }
 
-#[reducer]
-pub fn on_player_deleted(ctx: &ReducerContext, player: StdbPlayer) -> Result<(), String> {
-    ctx.db.player().delete(player);
-    Ok(())
+/// Marks the caller's player offline, keeping its row for when they return.
+pub fn disconnect_player(ctx: &ReducerContext) {
+    let players = ctx.db.player();
+    if let Some(player) = players.identity().find(ctx.sender) {
+        players.identity().update(StdbPlayer { online: false, ..player });
+    }
 }
 
+/// Moves the caller's player towards `transform`. Moves faster than `MAX_SPEED` allows since
+/// the last update are cut short, and the result is kept above stored ground and inside the world.
+/// `client_time` is the client's clock when it sampled the transform.
+#[reducer]
+pub fn move_player(ctx: &ReducerContext, transform: StdbTransform, client_time: Timestamp) -> Result<(), String> {
+    let players = ctx.db.player();
+    let player = players.identity().find(ctx.sender).ok_or("player does not exist")?;
+    if !player.online {
+        return Err("player is offline".into());
+    }
+    if !transform.is_finite() {
+        return Err("transform must be finite".into());
+    }
+    let rotation = transform.rotation.normalized().ok_or("rotation must not be zero")?;
+    if client_time.to_micros_since_unix_epoch() <= player.last_client_time.to_micros_since_unix_epoch() {
+        return Err("update is older than the last accepted one".into());
+    }
 
+    let from: Vector3<f32> = player.transform.position.into();
+    let to: Vector3<f32> = transform.position.into();
+    let distance = (to - from).norm();
+    if distance > TELEPORT_DISTANCE {
+        return Err(format!("moved {:.1} blocks in one update; at most {} are allowed", distance, TELEPORT_DISTANCE));
+    }
 
+    // credit the client's elapsed time, but never much more than the server has seen pass
+    let server_elapsed = ctx.timestamp.duration_since(player.last_update).unwrap_or_default().as_secs_f32();
+    let client_elapsed = client_time.duration_since(player.last_client_time).unwrap_or_default().as_secs_f32();
+    let elapsed = client_elapsed.min(server_elapsed + CLOCK_SLACK).min(MAX_CREDITED_GAP);
+    let allowed = MAX_SPEED * SPEED_TOLERANCE * elapsed;
+    let mut position = if distance > allowed { from + (to - from) * (allowed / distance) } else { to };
 
+    position.x = position.x.clamp(-WORLD_EXTENT, WORLD_EXTENT);
+    position.z = position.z.clamp(-WORLD_EXTENT, WORLD_EXTENT);
+    // only stored chunks are checked, so a move never builds the terrain generator; the
+    // chunks around a player are stored as soon as its client requests them
+    if let Some(ground) = TerrainQuery::new(ctx).stored_height_at(position.x, position.z) {
+        position.y = position.y.max(ground + GROUND_CLEARANCE);
+    }
+    position.y = position.y.min(WORLD_CEILING);
 
+    players.identity().update(StdbPlayer {
+        transform: StdbTransform { position: position.into(), rotation },
+        last_update: ctx.timestamp,
+        last_client_time: client_time,
+        ..player
+    });
+    Ok(())
+}
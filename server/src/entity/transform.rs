@@ -0,0 +1,59 @@
+// src/entity/transform.rs
+
+use nalgebra::{Quaternion, UnitQuaternion, Vector3};
+use spacetimedb::SpacetimeType;
+
+#[derive(SpacetimeType)]
+#[derive(Clone, Copy, Debug)]
+pub struct StdbPosition {
+    pub x: f32,
+    pub y: f32,
+    pub z: f32,
+}
+
+#[derive(SpacetimeType)]
+#[derive(Clone, Copy, Debug)]
+pub struct StdbRotation {
+    pub x: f32,
+    pub y: f32,
+    pub z: f32,
+    pub w: f32,
+}
+
+#[derive(SpacetimeType)]
+#[derive(Clone, Copy, Debug)]
+pub struct StdbTransform {
+    pub position: StdbPosition,
+    pub rotation: StdbRotation,
+}
+
+impl StdbTransform {
+    /// Whether every component is a real number.
+    pub fn is_finite(&self) -> bool {
+        let StdbPosition { x, y, z } = self.position;
+        let StdbRotation { x: rx, y: ry, z: rz, w } = self.rotation;
+        [x, y, z, rx, ry, rz, w].iter().all(|v| v.is_finite())
+    }
+}
+
+impl From<StdbPosition> for Vector3<f32> {
+    fn from(p: StdbPosition) -> Self {
+        Vector3::new(p.x, p.y, p.z)
+    }
+}
+
+impl From<Vector3<f32>> for StdbPosition {
+    fn from(v: Vector3<f32>) -> Self {
+        StdbPosition { x: v.x, y: v.y, z: v.z }
+    }
+}
+
+impl StdbRotation {
+    pub const IDENTITY: StdbRotation = StdbRotation { x: 0.0, y: 0.0, z: 0.0, w: 1.0 };
+
+    /// The rotation scaled to unit length, or `None` if it has no length to scale.
+    pub fn normalized(&self) -> Option<Self> {
+        let q = UnitQuaternion::try_new(Quaternion::new(self.w, self.x, self.y, self.z), f32::EPSILON)?;
+        Some(StdbRotation { x: q.i, y: q.j, z: q.k, w: q.w })
+    }
+}
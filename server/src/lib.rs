@@ -4,16 +4,22 @@ pub mod terrain;
 pub mod entity;
 
 #[reducer(init)]
-pub fn init(_ctx: &ReducerContext) {
+pub fn init(ctx: &ReducerContext) {
     // Called when the module is initially published
+    terrain::world::init_world_config(ctx);
+    terrain::material::init_materials(ctx);
+    terrain::sweep::schedule_stale_chunk_sweep(ctx);
+    terrain::queue::init_generation_queue(ctx);
 }
 
 #[reducer(client_connected)]
-pub fn identity_connected(_ctx: &ReducerContext) {
+pub fn identity_connected(ctx: &ReducerContext) {
     // Called everytime a new client connects
+    entity::player::connect_player(ctx);
 }
 
 #[reducer(client_disconnected)]
-pub fn identity_disconnected(_ctx: &ReducerContext) {
+pub fn identity_disconnected(ctx: &ReducerContext) {
     // Called everytime a client disconnects
+    entity::player::disconnect_player(ctx);
 }
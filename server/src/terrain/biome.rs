@@ -0,0 +1,70 @@
+// src/terrain/biome.rs
+
+use spacetimedb::{table, ReducerContext, Table};
+
+use crate::terrain::coords::{BiomeId, XZCoords, CHUNK_SIZE};
+use crate::terrain::generator::{Biome, BiomeGenerator};
+
+/// The biome each generated chunk column belongs to, taken at the column's centre.
+/// Heights and materials blend between biomes per sample; this is the dominant one.
+#[table(
+    name = chunk_biome,
+    index(name = idx_grid_xz, btree(columns = [grid_x, grid_z])),
+    public
+)]
+#[derive(Clone, Debug)]
+pub struct ChunkBiome {
+    /// `grid.key()`, so the chunk can be found by primary key.
+    #[primary_key]
+    pub key: i64,
+    pub grid: XZCoords,
+    pub grid_x: i32,
+    pub grid_z: i32,
+    /// `Biome` as its id.
+    pub biome: BiomeId,
+    pub temperature: f32,
+    pub moisture: f32,
+}
+
+impl ChunkBiome {
+    pub fn biome(&self) -> Option<Biome> {
+        Biome::from_id(self.biome)
+    }
+
+    /// The row `biomes` gives a chunk column.
+    pub fn generate(coord: XZCoords, biomes: &BiomeGenerator) -> Self {
+        let center = coord.to_world_pos(CHUNK_SIZE / 2, CHUNK_SIZE / 2);
+        let (x, z) = (center.x as f64, center.z as f64);
+        let (temperature, moisture) = biomes.climate_at(x, z);
+        Self {
+            key: coord.key(),
+            grid: coord,
+            grid_x: coord.x,
+            grid_z: coord.z,
+            biome: biomes.biome_at(x, z).id(),
+            temperature,
+            moisture,
+        }
+    }
+
+    /// Whether both rows hold the same biome and climate.
+    pub fn same_climate(&self, other: &ChunkBiome) -> bool {
+        self.biome == other.biome && self.temperature == other.temperature && self.moisture == other.moisture
+    }
+}
+
+/// Records the biome of a chunk column, or updates the stored row in place when the world's
+/// seed or climate has changed since.
+pub fn store_chunk_biome(ctx: &ReducerContext, coord: XZCoords, biomes: &BiomeGenerator) {
+    let row = ChunkBiome::generate(coord, biomes);
+    let table = ctx.db.chunk_biome();
+    match table.key().find(coord.key()) {
+        Some(stored) if stored.same_climate(&row) => {}
+        Some(_) => {
+            table.key().update(row);
+        }
+        None => {
+            table.insert(row);
+        }
+    }
+}
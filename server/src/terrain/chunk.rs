@@ -2,39 +2,54 @@
 
 use spacetimedb::{table, reducer, ReducerContext, Table};
 
-use crate::terrain::coords::XZCoords;
-use crate::terrain::generator::{HeightmapGenerator, MeshGenerator};
+use crate::terrain::biome::store_chunk_biome;
+use crate::terrain::coords::{MaterialId, XZCoords};
+use crate::terrain::edit::chunk_edit;
+use crate::terrain::generator::{apply_height_edits, pack_lod_mesh, MaterialGenerator, MeshGenerator, PackedMesh, PaddedHeightmap, TerrainGenerator, LOD_LEVELS};
+use crate::terrain::prop::store_chunk_props;
+use crate::terrain::structure::store_structures;
+use crate::terrain::water::store_chunk_water;
+use crate::terrain::world::WorldConfig;
 use once_cell::sync::OnceCell;
 
-static HEIGHTMAP_GENERATOR: OnceCell<HeightmapGenerator> = OnceCell::new();
-static MESH_GENERATOR: OnceCell<MeshGenerator> = OnceCell::new();
+pub(crate) static MESH_GENERATOR: OnceCell<MeshGenerator> = OnceCell::new();
 
+/// Most chunks a single `request_chunk_region` call may cover, to bound the transaction.
+pub const MAX_REGION_CHUNKS: i64 = 81;
+
+/// Server-side record of a generated chunk: its heights, full-detail mesh and the generator
+/// version that built it. Clients get the chunk from `chunk_packed` instead.
 #[table(
     name = chunk_vertex,
-    index(name = idx_grid_xz, btree(columns = [grid_x, grid_z])),
-    public
+    index(name = idx_grid_xz, btree(columns = [grid_x, grid_z]))
 )]
 #[derive(Clone, Debug)]
 pub struct ChunkVertex {
-    // these need a primary key that's unique, and the composite key works for that
-    // but i can't query the composite key in a spatial filter, so i need grid_x and grid_z for that
+    // the composite coords can't be looked up by primary key or queried in a spatial filter,
+    // so the key packs them into an integer and grid_x and grid_z are there for the filters
+    /// `grid.key()`, so the chunk can be found by primary key.
     #[primary_key]
+    pub key: i64,
     pub grid: XZCoords,
     pub grid_x: i32,
     pub grid_z: i32,
     pub heightmap: Vec<f32>,
     pub vertices: Vec<f32>,
     pub normals: Vec<f32>,
+    /// `WorldConfig::current_generator_version` when the chunk was built.
+    #[index(btree)]
+    pub generator_version: u32,
 }
 
 #[table(
     name = chunk_mesh, 
-    index(name = idx_grid_xz, btree(columns = [grid_x, grid_z])), 
-    public
+    index(name = idx_grid_xz, btree(columns = [grid_x, grid_z]))
 )]
 #[derive(Clone, Debug)]
 pub struct ChunkMesh {
+    /// `grid.key()`, so the chunk can be found by primary key.
     #[primary_key]
+    pub key: i64,
     pub grid: XZCoords,
     pub grid_x: i32,
     pub grid_z: i32,
@@ -43,40 +58,205 @@ pub struct ChunkMesh {
     pub materials: Vec<u32>,
 }
 
+/// Simplified copies of a chunk's surface for distant rendering, one row per chunk and level.
+/// Level `n` keeps every `1 << n`-th height sample and has skirts along its edges, so clients
+/// can mix levels between neighbouring chunks without cracks. Kept server-side at full
+/// precision; clients get each level from `chunk_packed`.
+#[table(
+    name = chunk_mesh_lod,
+    index(name = idx_grid_lod, btree(columns = [grid_x, grid_z, lod]))
+)]
+#[derive(Clone, Debug)]
+pub struct ChunkMeshLod {
+    #[primary_key]
+    #[auto_inc]
+    pub id: u64,
+    pub grid: XZCoords,
+    pub grid_x: i32,
+    pub grid_z: i32,
+    pub lod: u8,
+    pub vertices: Vec<f32>,
+    pub normals: Vec<f32>,
+    pub indices: Vec<u32>,
+    pub materials: Vec<u32>,
+}
+
+/// Compact copy of each `chunk_mesh_lod` row for clients to subscribe to. Positions and
+/// indices are implied by the grid, heights are quantized to u16 and normals octahedral
+/// encoded; see `PackedMesh` for the layout.
+#[table(
+    name = chunk_packed,
+    index(name = idx_grid_lod, btree(columns = [grid_x, grid_z, lod])),
+    public
+)]
+#[derive(Clone, Debug)]
+pub struct ChunkPacked {
+    #[primary_key]
+    #[auto_inc]
+    pub id: u64,
+    pub grid: XZCoords,
+    pub grid_x: i32,
+    pub grid_z: i32,
+    pub lod: u8,
+    pub height_min: f32,
+    pub height_step: f32,
+    pub heights: Vec<u16>,
+    pub normals: Vec<u8>,
+    pub skirt_depths: Vec<f32>,
+    pub palette: Vec<MaterialId>,
+    pub materials: Vec<u8>,
+}
+
+impl ChunkPacked {
+    pub fn new(coord: XZCoords, lod: u8, packed: PackedMesh) -> Self {
+        Self {
+            id: 0,
+            grid: coord,
+            grid_x: coord.x,
+            grid_z: coord.z,
+            lod,
+            height_min: packed.height_min,
+            height_step: packed.height_step,
+            heights: packed.heights,
+            normals: packed.normals,
+            skirt_depths: packed.skirt_depths,
+            palette: packed.palette,
+            materials: packed.materials,
+        }
+    }
+}
+
 #[reducer]
 pub fn on_chunk_requested(
     ctx: &ReducerContext,
     coord: XZCoords,
 ) -> Result<(), String> {
-    let chunk_vertex_table = ctx.db.chunk_vertex();
-    let chunk_mesh_table = ctx.db.chunk_mesh();
+    let config = WorldConfig::load(ctx);
+    if !is_chunk_current(ctx, coord, &config) {
+        generate_chunk(ctx, coord, &config);
+    }
+    Ok(())
+}
 
-    let neighborhood = chunk_vertex_table.iter()
-        .filter(|chunk| {
-            chunk.grid_x.abs_diff(coord.x) <= 1 && chunk.grid_z.abs_diff(coord.z) <= 1
-        })
-        .collect::<Vec<_>>();
-    // info!("Neighborhood: {:?}", neighborhood.iter().map(|chunk| chunk.coord).collect::<Vec<_>>());
+/// Generates every missing or stale chunk in the inclusive rectangle `min..=max` in one
+/// transaction, instead of a reducer call per chunk.
+#[reducer]
+pub fn request_chunk_region(
+    ctx: &ReducerContext,
+    min: XZCoords,
+    max: XZCoords,
+) -> Result<(), String> {
+    if min.x > max.x || min.z > max.z {
+        return Err("region min must not be greater than max".into());
+    }
+    let area = (max.x as i64 - min.x as i64 + 1) * (max.z as i64 - min.z as i64 + 1);
+    if area > MAX_REGION_CHUNKS {
+        return Err(format!("region covers {} chunks; at most {} can be requested at once", area, MAX_REGION_CHUNKS));
+    }
 
-    let mesh_generator = MESH_GENERATOR
-        .get_or_init(|| MeshGenerator::new());
+    let config = WorldConfig::load(ctx);
+    for z in min.z..=max.z {
+        for x in min.x..=max.x {
+            let coord = XZCoords { x, z };
+            if !is_chunk_current(ctx, coord, &config) {
+                generate_chunk(ctx, coord, &config);
+            }
+        }
+    }
+    Ok(())
+}
 
-    let padded_heightmap =  HEIGHTMAP_GENERATOR
-        .get_or_init(|| HeightmapGenerator::new(42))
-        .generate_padded_heightmap(coord);
+/// The keyed lookups chunk freshness is decided by, so `chunk_is_current` can also run
+/// against an in-memory index, e.g. in `benches/chunk_lookup.rs`.
+pub trait StoredChunks {
+    /// Generator version the stored chunk was built with, if it is stored at all.
+    fn chunk_version(&self, coord: XZCoords) -> Option<u32>;
+    /// Whether the chunk's mesh, its `lod` level mesh and the packed copy of it are stored.
+    fn has_meshes(&self, coord: XZCoords, lod: u8) -> bool;
+}
 
-    let chunk_mesh = mesh_generator.generate_dual_contour_mesh(coord, &padded_heightmap, &neighborhood);
+impl StoredChunks for ReducerContext {
+    fn chunk_version(&self, coord: XZCoords) -> Option<u32> {
+        self.db.chunk_vertex().key().find(coord.key())
+            .map(|chunk| chunk.generator_version)
+    }
+
+    fn has_meshes(&self, coord: XZCoords, lod: u8) -> bool {
+        self.db.chunk_mesh().key().find(coord.key()).is_some()
+            && self.db.chunk_mesh_lod().idx_grid_lod().filter((coord.x, coord.z, lod)).next().is_some()
+            && self.db.chunk_packed().idx_grid_lod().filter((coord.x, coord.z, lod)).next().is_some()
+    }
+}
+
+/// Whether the chunk is stored, built by `generator_version` or later, and has its LODs.
+/// Chunks stored before LOD meshes or their packed copies existed count as stale. Only primary
+/// key and index lookups, so the cost grows with the depth of the indexes rather than the
+/// number of chunks.
+pub fn chunk_is_current(stored: &impl StoredChunks, coord: XZCoords, generator_version: u32) -> bool {
+    stored.chunk_version(coord).is_some_and(|version| version >= generator_version)
+        && stored.has_meshes(coord, 0)
+}
+
+pub(crate) fn is_chunk_current(ctx: &ReducerContext, coord: XZCoords, config: &WorldConfig) -> bool {
+    chunk_is_current(ctx, coord, config.current_generator_version())
+}
+
+/// Generates a chunk, or rebuilds it in place, with the current config and every player edit
+/// that reaches it, along with its LOD meshes, water, props and biome.
+pub fn generate_chunk(ctx: &ReducerContext, coord: XZCoords, config: &WorldConfig) {
+    let terrain_generator = config.terrain_generator(ctx);
+    let padded_heightmap = load_padded_heightmap(ctx, coord, terrain_generator.as_ref());
+    let structures = terrain_generator.structures_touching(coord);
+
+    let material_generator = config.material_generator(ctx);
+    let (chunk_vertex, chunk_mesh) = build_chunk(coord, &padded_heightmap, &material_generator, config.current_generator_version());
+    store_chunk(ctx, chunk_vertex, chunk_mesh);
+    store_chunk_lods(ctx, coord, &padded_heightmap, &material_generator);
+    store_chunk_water(ctx, coord, &padded_heightmap, config.sea_level);
+    store_chunk_props(ctx, coord, &padded_heightmap, &config.prop_scatter(), &structures);
+    store_structures(ctx, coord, &structures);
+    store_chunk_biome(ctx, coord, &config.biome_generator());
+}
+
+/// Generated heights for a chunk plus every player edit that reaches into its padded area.
+pub fn load_padded_heightmap(
+    ctx: &ReducerContext,
+    coord: XZCoords,
+    generator: &dyn TerrainGenerator,
+) -> PaddedHeightmap {
+    let mut padded = generator.generate_padded_heightmap(coord);
+    apply_height_edits(&mut padded, coord, |neighbor| {
+        ctx.db.chunk_edit().key().find(neighbor.key()).map(|edit| edit.deltas)
+    });
+    padded
+}
+
+/// Meshes a chunk from its padded heightmap alone.
+pub fn build_chunk(
+    coord: XZCoords,
+    padded_heightmap: &PaddedHeightmap,
+    material_generator: &MaterialGenerator,
+    generator_version: u32,
+) -> (ChunkVertex, ChunkMesh) {
+    let mesh_generator = MESH_GENERATOR
+        .get_or_init(MeshGenerator::new);
+
+    let mut chunk_mesh = mesh_generator.generate_dual_contour_mesh(coord, padded_heightmap);
+    chunk_mesh.materials = material_generator.assign_materials(&chunk_mesh);
 
     let chunk_vertex = ChunkVertex {
+        key: coord.key(),
         grid: coord,
         grid_x: coord.x,
         grid_z: coord.z,
         heightmap: padded_heightmap.chunk_only(),
         vertices: chunk_mesh.vertices,
         normals: chunk_mesh.normals,
+        generator_version,
     };
 
     let chunk_mesh = ChunkMesh {
+        key: coord.key(),
         grid: coord,
         grid_x: coord.x,
         grid_z: coord.z,
@@ -84,25 +264,72 @@ pub fn on_chunk_requested(
         materials: chunk_mesh.materials,
     };
 
-    match chunk_vertex_table.try_insert(chunk_vertex.clone()) {
-        Ok(_) => {
-            // successfully inserted new chunk
-        }
-        Err(_) => {
-            // Chunk already existed—overwrite via update if desired
-            // chunk_vertex_table.update(chunk_vertex.clone());
+    (chunk_vertex, chunk_mesh)
+}
+
+/// Inserts the chunk rows, or updates them in place if the chunk was already generated.
+pub fn store_chunk(ctx: &ReducerContext, chunk_vertex: ChunkVertex, chunk_mesh: ChunkMesh) {
+    let chunk_vertex_table = ctx.db.chunk_vertex();
+    if chunk_vertex_table.key().find(chunk_vertex.key).is_some() {
+        chunk_vertex_table.key().update(chunk_vertex);
+    } else {
+        chunk_vertex_table.insert(chunk_vertex);
+    }
+
+    let chunk_mesh_table = ctx.db.chunk_mesh();
+    if chunk_mesh_table.key().find(chunk_mesh.key).is_some() {
+        chunk_mesh_table.key().update(chunk_mesh);
+    } else {
+        chunk_mesh_table.insert(chunk_mesh);
+    }
+}
+
+/// Meshes every level of detail of a chunk and inserts or updates its `chunk_mesh_lod` and
+/// `chunk_packed` rows.
+pub fn store_chunk_lods(
+    ctx: &ReducerContext,
+    coord: XZCoords,
+    padded_heightmap: &PaddedHeightmap,
+    material_generator: &MaterialGenerator,
+) {
+    let mesh_generator = MESH_GENERATOR
+        .get_or_init(MeshGenerator::new);
+
+    for lod in 0..LOD_LEVELS {
+        let mut mesh = mesh_generator.generate_lod_mesh(coord, padded_heightmap, lod);
+        mesh.materials = material_generator.assign_materials(&mesh);
+        store_chunk_packed(ctx, ChunkPacked::new(coord, lod, pack_lod_mesh(&mesh, lod)));
+
+        let row = ChunkMeshLod {
+            id: 0,
+            grid: coord,
+            grid_x: coord.x,
+            grid_z: coord.z,
+            lod,
+            vertices: mesh.vertices,
+            normals: mesh.normals,
+            indices: mesh.indices,
+            materials: mesh.materials,
+        };
+        match ctx.db.chunk_mesh_lod().idx_grid_lod().filter((coord.x, coord.z, lod)).next() {
+            Some(existing) => {
+                ctx.db.chunk_mesh_lod().id().update(ChunkMeshLod { id: existing.id, ..row });
+            }
+            None => {
+                ctx.db.chunk_mesh_lod().insert(row);
+            }
         }
     }
+}
 
-    match chunk_mesh_table.try_insert(chunk_mesh.clone()) {
-        Ok(_) => {
-            // successfully inserted new mesh
+/// Inserts a `chunk_packed` row, or updates the one already stored for its chunk and level.
+pub fn store_chunk_packed(ctx: &ReducerContext, row: ChunkPacked) {
+    match ctx.db.chunk_packed().idx_grid_lod().filter((row.grid_x, row.grid_z, row.lod)).next() {
+        Some(existing) => {
+            ctx.db.chunk_packed().id().update(ChunkPacked { id: existing.id, ..row });
         }
-        Err(_) => {
-            // Chunk already existed—overwrite via update if desired
-            // chunk_mesh_table.update(chunk_mesh.clone());
+        None => {
+            ctx.db.chunk_packed().insert(row);
         }
     }
-
-    Ok(())
 }
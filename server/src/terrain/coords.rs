@@ -1,5 +1,6 @@
 // src/coords.rs
 
+use nalgebra::Vector3;
 use spacetimedb::SpacetimeType;
 
 /// Chunk indices on the XZ plane.
@@ -18,8 +19,50 @@ impl XZCoords {
             z: (self.z * CHUNK_SIZE as i32 + local_z as i32) as f32,
         }
     }
+
+    /// Primary key for tables keyed by chunk column: `x` in the high 32 bits, `z` in the low.
+    /// The coordinates themselves can't be looked up by primary key.
+    pub fn key(&self) -> i64 {
+        ((self.x as i64) << 32) | (self.z as u32 as i64)
+    }
+}
+
+/// Chunk section indices: `x`/`z` pick the chunk column, `y` the vertical section within it.
+#[derive(SpacetimeType)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct XYZCoords {
+    pub x: i32,
+    pub y: i32,
+    pub z: i32,
 }
 
+impl XYZCoords {
+    pub fn to_world_pos(&self, local_x: i32, local_y: i32, local_z: i32) -> Vec3 {
+        Vec3 {
+            x: (self.x * CHUNK_SIZE + local_x) as f32,
+            y: (self.y * SECTION_SIZE + local_y) as f32,
+            z: (self.z * CHUNK_SIZE + local_z) as f32,
+        }
+    }
+
+    pub fn xz(&self) -> XZCoords {
+        XZCoords { x: self.x, z: self.z }
+    }
+
+    pub fn from_column(column: XZCoords, section: i32) -> Self {
+        Self { x: column.x, y: section, z: column.z }
+    }
+
+    /// Primary key for tables keyed by section: `x`, `y` and `z` in successive 32-bit lanes.
+    pub fn key(&self) -> i128 {
+        ((self.x as i128) << 64) | ((self.y as u32 as i128) << 32) | (self.z as u32 as i128)
+    }
+}
+
+/// Index of the vertical section containing world height `y`.
+pub fn section_of(y: f32) -> i32 {
+    (y / SECTION_SIZE as f32).floor() as i32
+}
 
 /// A 3D vector.
 #[derive(SpacetimeType)]
@@ -45,6 +88,18 @@ impl Vec3 {
     }
 }
 
+impl From<Vec3> for Vector3<f32> {
+    fn from(v: Vec3) -> Self {
+        Vector3::new(v.x, v.y, v.z)
+    }
+}
+
+impl From<Vector3<f32>> for Vec3 {
+    fn from(v: Vector3<f32>) -> Self {
+        Vec3::new(v.x, v.y, v.z)
+    }
+}
+
 /// A density update at a specific index.
 #[derive(SpacetimeType)]
 #[derive(Clone, Copy, Debug)]
@@ -56,6 +111,10 @@ pub struct DensityDelta {
 /// Which material (grass, dirt, stone…) a vertex/face belongs to.
 pub type MaterialId = u32;
 
+/// Which biome (plains, desert…) a column of terrain belongs to.
+pub type BiomeId = u32;
+
 /// How many voxels per edge of a chunk.
 pub const CHUNK_SIZE: i32 = 32;
+/// How many voxels tall a vertical section of a chunk column is.
 pub const SECTION_SIZE: i32 = 32;
\ No newline at end of file
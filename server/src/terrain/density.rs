@@ -1,18 +1,199 @@
 // src/density.rs
 
-// use spacetimedb::{table, reducer};
-// use crate::coords::{ChunkCoords, CHUNK_SIZE, MaterialId};
-
-// #[cfg_attr(feature="server", table(name="density_chunk"))]
-// #[cfg_attr(feature="server", derive(Clone, Debug))]
-// pub struct DensityChunk {
-//     #[cfg_attr(feature="server", spacetimedb(primary_key))]
-//     pub coord: ChunkCoords,
-//     pub densities: Vec<f32>,    // (CHUNK_SIZE+1)^3 samples
-//     pub materials: Vec<MaterialId>,
-// }
-
-// pub struct DensityUpdated {
-//     pub coord: ChunkCoords,
-//     pub deltas: Vec<(usize, f32)>,
-// }
+use std::collections::HashSet;
+
+use spacetimedb::{table, reducer, ReducerContext, Table};
+
+use crate::terrain::biome::store_chunk_biome;
+use crate::terrain::chunk::MESH_GENERATOR;
+use crate::terrain::coords::{MaterialId, XYZCoords, XZCoords, CHUNK_SIZE, SECTION_SIZE};
+use crate::terrain::edit::density_edit;
+use crate::terrain::generator::{pack_density_mesh, MaterialGenerator, MeshGenerator, PackedDensityMesh, PaddedDensity, TerrainGenerator, DENSITY_DIM};
+use crate::terrain::world::WorldConfig;
+
+/// Server-side record of a section's densities. Clients get the section from `density_mesh`.
+#[table(
+    name = density_chunk,
+    index(name = idx_grid_xyz, btree(columns = [grid_x, grid_y, grid_z]))
+)]
+#[derive(Clone, Debug)]
+pub struct DensityChunk {
+    /// `coord.key()`, so the section can be found by primary key.
+    #[primary_key]
+    pub key: i128,
+    pub coord: XYZCoords,
+    pub grid_x: i32,
+    pub grid_y: i32,
+    pub grid_z: i32,
+    /// (CHUNK_SIZE+1)^2 * (SECTION_SIZE+1) samples in `density_index` order; positive is solid.
+    pub densities: Vec<f32>,
+    /// `WorldConfig::current_generator_version` when the section was built.
+    #[index(btree)]
+    pub generator_version: u32,
+}
+
+/// A section's mesh in compact form: quantized positions, octahedral normals, u16 indices and
+/// a material palette; see `PackedDensityMesh` for the layout.
+#[table(
+    name = density_mesh,
+    index(name = idx_grid_xyz, btree(columns = [grid_x, grid_y, grid_z])),
+    public
+)]
+#[derive(Clone, Debug)]
+pub struct DensityMesh {
+    /// `coord.key()`, so the section can be found by primary key.
+    #[primary_key]
+    pub key: i128,
+    pub coord: XYZCoords,
+    pub grid_x: i32,
+    pub grid_y: i32,
+    pub grid_z: i32,
+    pub positions: Vec<u16>,
+    pub normals: Vec<u8>,
+    pub indices: Vec<u16>,
+    pub palette: Vec<MaterialId>,
+    pub materials: Vec<u8>,
+}
+
+impl DensityMesh {
+    pub fn new(coord: XYZCoords, packed: PackedDensityMesh) -> Self {
+        Self {
+            key: coord.key(),
+            coord,
+            grid_x: coord.x,
+            grid_y: coord.y,
+            grid_z: coord.z,
+            positions: packed.positions,
+            normals: packed.normals,
+            indices: packed.indices,
+            palette: packed.palette,
+            materials: packed.materials,
+        }
+    }
+}
+
+#[reducer]
+pub fn on_density_chunk_requested(
+    ctx: &ReducerContext,
+    coord: XYZCoords,
+) -> Result<(), String> {
+    let config = WorldConfig::load(ctx);
+    if is_current(ctx, coord, &config) {
+        return Ok(());
+    }
+
+    let padded_density = load_padded_density(ctx, coord, config.terrain_generator(ctx).as_ref());
+    store_density_chunk(ctx, coord, &padded_density, &config.material_generator(ctx), config.current_generator_version());
+
+    Ok(())
+}
+
+/// Generates every section of a chunk column that the surface passes through.
+/// Sections above and below can still be requested one at a time.
+#[reducer]
+pub fn on_column_requested(
+    ctx: &ReducerContext,
+    coord: XZCoords,
+) -> Result<(), String> {
+    let config = WorldConfig::load(ctx);
+    let terrain_generator = config.terrain_generator(ctx);
+    let material_generator = config.material_generator(ctx);
+
+    for section in terrain_generator.surface_sections(coord) {
+        let section_coord = XYZCoords::from_column(coord, section);
+        if is_current(ctx, section_coord, &config) {
+            continue;
+        }
+        let padded_density = load_padded_density(ctx, section_coord, terrain_generator.as_ref());
+        store_density_chunk(ctx, section_coord, &padded_density, &material_generator, config.current_generator_version());
+    }
+    store_chunk_biome(ctx, coord, &config.biome_generator());
+
+    Ok(())
+}
+
+/// Whether the section has been generated by the current generator version.
+fn is_current(ctx: &ReducerContext, coord: XYZCoords, config: &WorldConfig) -> bool {
+    ctx.db.density_chunk().key().find(coord.key())
+        .is_some_and(|section| section.generator_version >= config.current_generator_version())
+}
+
+/// Generated densities for a section plus every player edit that reaches into its padded area.
+pub fn load_padded_density(
+    ctx: &ReducerContext,
+    coord: XYZCoords,
+    generator: &dyn TerrainGenerator,
+) -> PaddedDensity {
+    let mut padded = generator.generate_padded_density(coord);
+    let dim = DENSITY_DIM as i32;
+    let padding = PaddedDensity::PADDING as i32;
+    let in_padded = |v: i32, size: i32| v >= -padding && v < size + padding;
+
+    // border samples are recorded in every chunk that shares them, so apply each one once
+    let mut applied = HashSet::new();
+    for dy in -1..=1 {
+        for dz in -1..=1 {
+            for dx in -1..=1 {
+                let neighbor = XYZCoords { x: coord.x + dx, y: coord.y + dy, z: coord.z + dz };
+                let Some(edit) = ctx.db.density_edit().key().find(neighbor.key()) else {
+                    continue;
+                };
+                for delta in &edit.deltas {
+                    let index = delta.index as i32;
+                    let x = index % dim + dx * CHUNK_SIZE;
+                    let z = (index / dim) % dim + dz * CHUNK_SIZE;
+                    let y = index / (dim * dim) + dy * SECTION_SIZE;
+                    if !(in_padded(x, CHUNK_SIZE) && in_padded(y, SECTION_SIZE) && in_padded(z, CHUNK_SIZE)) {
+                        continue;
+                    }
+                    if applied.insert((x, y, z)) {
+                        let (x, y, z) = (x as isize, y as isize, z as isize);
+                        padded.set(x, y, z, padded.get(x, y, z) + delta.value);
+                    }
+                }
+            }
+        }
+    }
+
+    padded
+}
+
+/// Meshes a density section and inserts its rows, or updates them in place if it already exists.
+pub fn store_density_chunk(
+    ctx: &ReducerContext,
+    coord: XYZCoords,
+    padded_density: &PaddedDensity,
+    material_generator: &MaterialGenerator,
+    generator_version: u32,
+) {
+    let mut mesh = MESH_GENERATOR
+        .get_or_init(MeshGenerator::new)
+        .generate_density_mesh(coord, padded_density);
+    mesh.materials = material_generator.assign_materials(&mesh);
+
+    let density_chunk = DensityChunk {
+        key: coord.key(),
+        coord,
+        grid_x: coord.x,
+        grid_y: coord.y,
+        grid_z: coord.z,
+        densities: padded_density.chunk_only(),
+        generator_version,
+    };
+
+    let density_mesh = DensityMesh::new(coord, pack_density_mesh(coord, &mesh));
+
+    let density_chunk_table = ctx.db.density_chunk();
+    if density_chunk_table.key().find(coord.key()).is_some() {
+        density_chunk_table.key().update(density_chunk);
+    } else {
+        density_chunk_table.insert(density_chunk);
+    }
+
+    let density_mesh_table = ctx.db.density_mesh();
+    if density_mesh_table.key().find(coord.key()).is_some() {
+        density_mesh_table.key().update(density_mesh);
+    } else {
+        density_mesh_table.insert(density_mesh);
+    }
+}
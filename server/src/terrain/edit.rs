@@ -0,0 +1,305 @@
+// src/terrain/edit.rs
+
+use std::collections::{BTreeSet, HashMap};
+
+use spacetimedb::{table, reducer, ReducerContext, Table};
+
+use crate::entity::player::{player, WORLD_CEILING, WORLD_EXTENT};
+use crate::terrain::chunk::{build_chunk, chunk_vertex, load_padded_heightmap, store_chunk, store_chunk_lods};
+use crate::terrain::coords::{DensityDelta, Vec3, XYZCoords, XZCoords, CHUNK_SIZE, SECTION_SIZE};
+use crate::terrain::density::{density_chunk, load_padded_density, store_density_chunk};
+use crate::terrain::generator::{
+    density_index, heightmap_index, merge_deltas, sculpt_density, sculpt_height,
+    MaterialGenerator, PaddedDensity, SculptMode, TerrainGenerator,
+};
+use crate::terrain::prop::store_chunk_props;
+use crate::terrain::water::store_chunk_water;
+use crate::terrain::world::WorldConfig;
+
+/// Largest sphere a single edit may use, to bound how many chunks one call remeshes.
+pub const MAX_EDIT_RADIUS: f32 = 8.0;
+/// Farthest an edit's center may be from the editing player.
+pub const MAX_EDIT_REACH: f32 = 16.0;
+
+/// Player edits to a chunk heightmap, layered on top of the generated heights.
+/// Indices are `heightmap_index` into the chunk's `(CHUNK_SIZE+1)^2` samples; values are height offsets.
+#[table(
+    name = chunk_edit,
+    index(name = idx_grid_xz, btree(columns = [grid_x, grid_z])),
+    public
+)]
+#[derive(Clone, Debug)]
+pub struct ChunkEdit {
+    /// `grid.key()`, so the chunk can be found by primary key.
+    #[primary_key]
+    pub key: i64,
+    pub grid: XZCoords,
+    pub grid_x: i32,
+    pub grid_z: i32,
+    pub deltas: Vec<DensityDelta>,
+}
+
+/// Player edits to a density section, layered on top of the generated densities.
+/// Indices are `density_index` into the section's samples; values are density offsets.
+#[table(
+    name = density_edit,
+    index(name = idx_grid_xyz, btree(columns = [grid_x, grid_y, grid_z])),
+    public
+)]
+#[derive(Clone, Debug)]
+pub struct DensityEdit {
+    /// `coord.key()`, so the section can be found by primary key.
+    #[primary_key]
+    pub key: i128,
+    pub coord: XYZCoords,
+    pub grid_x: i32,
+    pub grid_y: i32,
+    pub grid_z: i32,
+    pub deltas: Vec<DensityDelta>,
+}
+
+/// Removes terrain inside a sphere.
+#[reducer]
+pub fn dig_sphere(ctx: &ReducerContext, center: Vec3, radius: f32) -> Result<(), String> {
+    apply_sphere_edit(ctx, center, radius, SculptMode::Dig)
+}
+
+/// Adds terrain inside a sphere.
+#[reducer]
+pub fn fill_sphere(ctx: &ReducerContext, center: Vec3, radius: f32) -> Result<(), String> {
+    apply_sphere_edit(ctx, center, radius, SculptMode::Fill)
+}
+
+fn apply_sphere_edit(ctx: &ReducerContext, center: Vec3, radius: f32, mode: SculptMode) -> Result<(), String> {
+    if !(center.x.is_finite() && center.y.is_finite() && center.z.is_finite()) {
+        return Err("edit center must be finite".into());
+    }
+    if !(radius > 0.0 && radius <= MAX_EDIT_RADIUS) {
+        return Err(format!("edit radius must be in (0, {}]", MAX_EDIT_RADIUS));
+    }
+    if center.x.abs() > WORLD_EXTENT || center.z.abs() > WORLD_EXTENT || center.y > WORLD_CEILING {
+        return Err("edit center must be inside the world".into());
+    }
+    let player = ctx.db.player().identity().find(ctx.sender).ok_or("player does not exist")?;
+    if !player.online {
+        return Err("player is offline".into());
+    }
+    let position = player.transform.position;
+    let reach = ((center.x - position.x).powi(2) + (center.y - position.y).powi(2) + (center.z - position.z).powi(2)).sqrt();
+    if reach > MAX_EDIT_REACH {
+        return Err(format!("edit is {:.1} blocks away; at most {} are allowed", reach, MAX_EDIT_REACH));
+    }
+
+    let config = WorldConfig::load(ctx);
+    let material_generator = config.material_generator(ctx);
+    let terrain_generator = config.terrain_generator(ctx);
+    let generator_version = config.current_generator_version();
+    edit_heightmap(ctx, terrain_generator.as_ref(), &material_generator, &config, &center, radius, mode);
+    edit_density(ctx, terrain_generator.as_ref(), &material_generator, generator_version, &center, radius, mode);
+
+    Ok(())
+}
+
+/// Chunk indices along one axis whose local samples `0..=size` include world sample `g`.
+fn owning_chunks(g: i32, size: i32) -> impl Iterator<Item = (i32, usize)> {
+    let chunk = g.div_euclid(size);
+    let local = g.rem_euclid(size);
+    let shared = (local == 0).then_some((chunk - 1, size as usize));
+    std::iter::once((chunk, local as usize)).chain(shared)
+}
+
+/// Chunk indices along one axis whose padded area (`-pad_low..=size+pad_high` local)
+/// covers any of the world samples `min..=max`.
+fn chunks_reaching(min: i32, max: i32, size: i32, pad_low: i32, pad_high: i32) -> std::ops::RangeInclusive<i32> {
+    (min - pad_high - 1).div_euclid(size)..=(max + pad_low).div_euclid(size)
+}
+
+fn edit_heightmap(
+    ctx: &ReducerContext,
+    generator: &dyn TerrainGenerator,
+    material_generator: &MaterialGenerator,
+    config: &WorldConfig,
+    center: &Vec3,
+    radius: f32,
+    mode: SculptMode,
+) {
+    let edit_table = ctx.db.chunk_edit();
+    let mut existing: HashMap<XZCoords, HashMap<u32, f32>> = HashMap::new();
+    let mut new_deltas: HashMap<XZCoords, Vec<DensityDelta>> = HashMap::new();
+    let mut edited: Option<(i32, i32, i32, i32)> = None;
+
+    for gz in (center.z - radius).floor() as i32..=(center.z + radius).ceil() as i32 {
+        for gx in (center.x - radius).floor() as i32..=(center.x + radius).ceil() as i32 {
+            // current height is the generated height plus whatever has been dug or filled before
+            let home = XZCoords { x: gx.div_euclid(CHUNK_SIZE), z: gz.div_euclid(CHUNK_SIZE) };
+            let home_index = heightmap_index(gx.rem_euclid(CHUNK_SIZE) as usize, gz.rem_euclid(CHUNK_SIZE) as usize) as u32;
+            let offsets = existing.entry(home).or_insert_with(|| {
+                edit_table.key().find(home.key())
+                    .map(|edit| edit.deltas.iter().map(|d| (d.index, d.value)).collect())
+                    .unwrap_or_default()
+            });
+            let height = generator.sample_height(gx as f64, gz as f64)
+                + offsets.get(&home_index).copied().unwrap_or(0.0);
+
+            let Some(new_height) = sculpt_height(mode, height, center, radius, gx as f32, gz as f32) else {
+                continue;
+            };
+
+            for (cx, lx) in owning_chunks(gx, CHUNK_SIZE) {
+                for (cz, lz) in owning_chunks(gz, CHUNK_SIZE) {
+                    new_deltas.entry(XZCoords { x: cx, z: cz }).or_default().push(DensityDelta {
+                        index: heightmap_index(lx, lz) as u32,
+                        value: new_height - height,
+                    });
+                }
+            }
+            edited = Some(match edited {
+                None => (gx, gx, gz, gz),
+                Some((x0, x1, z0, z1)) => (x0.min(gx), x1.max(gx), z0.min(gz), z1.max(gz)),
+            });
+        }
+    }
+
+    let Some((min_x, max_x, min_z, max_z)) = edited else {
+        return;
+    };
+
+    for (grid, deltas) in new_deltas {
+        match edit_table.key().find(grid.key()) {
+            Some(mut edit) => {
+                merge_deltas(&mut edit.deltas, &deltas);
+                edit_table.key().update(edit);
+            }
+            None => {
+                let mut merged = Vec::new();
+                merge_deltas(&mut merged, &deltas);
+                edit_table.insert(ChunkEdit { key: grid.key(), grid, grid_x: grid.x, grid_z: grid.z, deltas: merged });
+            }
+        }
+    }
+
+    // vertices read samples from one before to one past the chunk, so remesh every generated
+    // chunk whose padded heightmap covers an edited sample
+    let mut affected = BTreeSet::new();
+    for x in chunks_reaching(min_x, max_x, CHUNK_SIZE, 1, 1) {
+        for z in chunks_reaching(min_z, max_z, CHUNK_SIZE, 1, 1) {
+            if ctx.db.chunk_vertex().key().find(XZCoords { x, z }.key()).is_some() {
+                affected.insert((x, z));
+            }
+        }
+    }
+
+    let prop_scatter = config.prop_scatter();
+    for (x, z) in affected {
+        let coord = XZCoords { x, z };
+        let padded_heightmap = load_padded_heightmap(ctx, coord, generator);
+        let (chunk_vertex, chunk_mesh) = build_chunk(coord, &padded_heightmap, material_generator, config.current_generator_version());
+        store_chunk(ctx, chunk_vertex, chunk_mesh);
+        store_chunk_lods(ctx, coord, &padded_heightmap, material_generator);
+        store_chunk_water(ctx, coord, &padded_heightmap, config.sea_level);
+        // re-seat props on the new ground, dropping any that now stand on a cliff or under water
+        store_chunk_props(ctx, coord, &padded_heightmap, &prop_scatter, &generator.structures_touching(coord));
+    }
+}
+
+fn edit_density(
+    ctx: &ReducerContext,
+    generator: &dyn TerrainGenerator,
+    material_generator: &MaterialGenerator,
+    generator_version: u32,
+    center: &Vec3,
+    radius: f32,
+    mode: SculptMode,
+) {
+    let edit_table = ctx.db.density_edit();
+    let mut existing: HashMap<XYZCoords, HashMap<u32, f32>> = HashMap::new();
+    let mut new_deltas: HashMap<XYZCoords, Vec<DensityDelta>> = HashMap::new();
+    let mut edited: Option<([i32; 3], [i32; 3])> = None;
+
+    let range = |c: f32| (c - radius).floor() as i32..=(c + radius).ceil() as i32;
+    for gy in range(center.y) {
+        for gz in range(center.z) {
+            for gx in range(center.x) {
+                let home = XYZCoords {
+                    x: gx.div_euclid(CHUNK_SIZE),
+                    y: gy.div_euclid(SECTION_SIZE),
+                    z: gz.div_euclid(CHUNK_SIZE),
+                };
+                let home_index = density_index(
+                    gx.rem_euclid(CHUNK_SIZE) as usize,
+                    gy.rem_euclid(SECTION_SIZE) as usize,
+                    gz.rem_euclid(CHUNK_SIZE) as usize,
+                ) as u32;
+                let offsets = existing.entry(home).or_insert_with(|| {
+                    edit_table.key().find(home.key())
+                        .map(|edit| edit.deltas.iter().map(|d| (d.index, d.value)).collect())
+                        .unwrap_or_default()
+                });
+                let density = generator.density_at(gx as f64, gy as f64, gz as f64)
+                    + offsets.get(&home_index).copied().unwrap_or(0.0);
+
+                let new_density = sculpt_density(mode, density, center, radius, gx as f32, gy as f32, gz as f32);
+                if new_density == density {
+                    continue;
+                }
+
+                for (cx, lx) in owning_chunks(gx, CHUNK_SIZE) {
+                    for (cy, ly) in owning_chunks(gy, SECTION_SIZE) {
+                        for (cz, lz) in owning_chunks(gz, CHUNK_SIZE) {
+                            new_deltas.entry(XYZCoords { x: cx, y: cy, z: cz }).or_default().push(DensityDelta {
+                                index: density_index(lx, ly, lz) as u32,
+                                value: new_density - density,
+                            });
+                        }
+                    }
+                }
+                edited = Some(match edited {
+                    None => ([gx, gy, gz], [gx, gy, gz]),
+                    Some((lo, hi)) => (
+                        [lo[0].min(gx), lo[1].min(gy), lo[2].min(gz)],
+                        [hi[0].max(gx), hi[1].max(gy), hi[2].max(gz)],
+                    ),
+                });
+            }
+        }
+    }
+
+    let Some((lo, hi)) = edited else {
+        return;
+    };
+
+    for (coord, deltas) in new_deltas {
+        match edit_table.key().find(coord.key()) {
+            Some(mut edit) => {
+                merge_deltas(&mut edit.deltas, &deltas);
+                edit_table.key().update(edit);
+            }
+            None => {
+                let mut merged = Vec::new();
+                merge_deltas(&mut merged, &deltas);
+                edit_table.insert(DensityEdit {
+                    key: coord.key(),
+                    coord,
+                    grid_x: coord.x,
+                    grid_y: coord.y,
+                    grid_z: coord.z,
+                    deltas: merged,
+                });
+            }
+        }
+    }
+
+    // cells and gradients read samples two before to one past the section
+    let (pad_low, pad_high) = (PaddedDensity::PADDING as i32, PaddedDensity::PADDING as i32 - 1);
+    for x in chunks_reaching(lo[0], hi[0], CHUNK_SIZE, pad_low, pad_high) {
+        for y in chunks_reaching(lo[1], hi[1], SECTION_SIZE, pad_low, pad_high) {
+            for z in chunks_reaching(lo[2], hi[2], CHUNK_SIZE, pad_low, pad_high) {
+                let coord = XYZCoords { x, y, z };
+                if ctx.db.density_chunk().key().find(coord.key()).is_some() {
+                    let padded_density = load_padded_density(ctx, coord, generator);
+                    store_density_chunk(ctx, coord, &padded_density, material_generator, generator_version);
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,113 @@
+use noise::{NoiseFn, Perlin};
+
+use crate::terrain::coords::BiomeId;
+
+/// How far apart two climates can be before one biome stops blending into the other.
+const BLEND_WIDTH: f32 = 0.25;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[repr(u32)]
+pub enum Biome {
+    Plains = 0,
+    Desert = 1,
+    Mountains = 2,
+    Tundra = 3,
+    Swamp = 4,
+}
+
+/// Shape of the terrain in a biome, as fractions of the world's height range:
+/// height = (offset + scale * noise) * height_range, with noise in -1..1.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct BiomeProfile {
+    pub offset: f32,
+    pub scale: f32,
+}
+
+impl Biome {
+    pub const ALL: [Biome; 5] = [Biome::Plains, Biome::Desert, Biome::Mountains, Biome::Tundra, Biome::Swamp];
+
+    pub fn id(self) -> BiomeId {
+        self as BiomeId
+    }
+
+    pub fn from_id(id: BiomeId) -> Option<Self> {
+        Self::ALL.into_iter().find(|biome| biome.id() == id)
+    }
+
+    /// The (temperature, moisture) this biome is most typical of, both in -1..1.
+    fn climate(self) -> (f32, f32) {
+        match self {
+            Biome::Plains => (0.1, 0.0),
+            Biome::Desert => (0.7, -0.6),
+            Biome::Mountains => (-0.2, -0.5),
+            Biome::Tundra => (-0.7, 0.1),
+            Biome::Swamp => (0.4, 0.7),
+        }
+    }
+
+    /// Every profile keeps |offset| + scale <= 1, so heights stay within the height range.
+    pub fn profile(self) -> BiomeProfile {
+        match self {
+            Biome::Plains => BiomeProfile { offset: 0.1, scale: 0.25 },
+            Biome::Desert => BiomeProfile { offset: 0.05, scale: 0.15 },
+            Biome::Mountains => BiomeProfile { offset: 0.35, scale: 0.6 },
+            Biome::Tundra => BiomeProfile { offset: 0.15, scale: 0.35 },
+            Biome::Swamp => BiomeProfile { offset: -0.05, scale: 0.05 },
+        }
+    }
+}
+
+/// Temperature and moisture noise that decide which biome covers each column.
+pub struct BiomeGenerator {
+    temperature: Perlin,
+    moisture: Perlin,
+    climate_frequency: f64,
+}
+
+impl BiomeGenerator {
+    pub fn new(seed: u32, climate_frequency: f64) -> Self {
+        Self {
+            // offset the seeds so climate doesn't line up with the other noise layers
+            temperature: Perlin::new(seed.wrapping_add(4)),
+            moisture: Perlin::new(seed.wrapping_add(5)),
+            climate_frequency,
+        }
+    }
+
+    /// (temperature, moisture) at a world position, both in -1..1.
+    pub fn climate_at(&self, x: f64, z: f64) -> (f32, f32) {
+        let sample = [x * self.climate_frequency, z * self.climate_frequency];
+        (
+            (self.temperature.get(sample) as f32).clamp(-1.0, 1.0),
+            (self.moisture.get(sample) as f32).clamp(-1.0, 1.0),
+        )
+    }
+
+    /// Normalised blend weight of every biome at a world position, in `Biome::ALL` order.
+    /// Weights fall off smoothly with climate distance, so blended heights have no seams.
+    pub fn weights_at(&self, x: f64, z: f64) -> [f32; 5] {
+        let (temperature, moisture) = self.climate_at(x, z);
+        let mut weights = Biome::ALL.map(|biome| {
+            let (t, m) = biome.climate();
+            let distance_sq = (temperature - t).powi(2) + (moisture - m).powi(2);
+            (-distance_sq / (BLEND_WIDTH * BLEND_WIDTH)).exp()
+        });
+        let total: f32 = weights.iter().sum();
+        weights.iter_mut().for_each(|w| *w /= total);
+        weights
+    }
+
+    /// The biome whose climate is closest at a world position.
+    pub fn biome_at(&self, x: f64, z: f64) -> Biome {
+        let (temperature, moisture) = self.climate_at(x, z);
+        Biome::ALL.into_iter()
+            .min_by(|a, b| {
+                let distance = |biome: &Biome| {
+                    let (t, m) = biome.climate();
+                    (temperature - t).powi(2) + (moisture - m).powi(2)
+                };
+                distance(a).total_cmp(&distance(b))
+            })
+            .unwrap_or(Biome::Plains)
+    }
+}
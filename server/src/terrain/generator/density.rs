@@ -0,0 +1,194 @@
+use noise::{NoiseFn, Perlin};
+
+use crate::terrain::coords::{XYZCoords, CHUNK_SIZE, SECTION_SIZE};
+use crate::terrain::generator::HeightmapGenerator;
+
+/// How far below the cave threshold the density recovers to fully solid.
+const CAVE_SCALE: f32 = 32.0;
+
+/// 3D noise parameters layered on top of the heightmap surface.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct DensitySettings {
+    pub cave_frequency: f64,
+    /// Cave noise above this value is carved out; higher means fewer, thinner caves.
+    pub cave_threshold: f32,
+    pub overhang_frequency: f64,
+    /// How many blocks the surface can be pushed in or out to form overhangs and cliffs.
+    pub overhang_strength: f32,
+}
+
+impl Default for DensitySettings {
+    fn default() -> Self {
+        Self {
+            cave_frequency: 0.04,
+            cave_threshold: 0.45,
+            overhang_frequency: 0.03,
+            overhang_strength: 6.0,
+        }
+    }
+}
+
+/// Number of samples along x and z of a stored density section (the corners of CHUNK_SIZE cells).
+pub const DENSITY_DIM: usize = CHUNK_SIZE as usize + 1;
+/// Number of samples along y of a stored density section (the corners of SECTION_SIZE cells).
+pub const SECTION_DIM: usize = SECTION_SIZE as usize + 1;
+
+/// Index of a local sample in a density section array, x fastest, then z, then y.
+pub fn density_index(x: usize, y: usize, z: usize) -> usize {
+    (y * DENSITY_DIM + z) * DENSITY_DIM + x
+}
+
+/// Density samples for a chunk section with two samples of padding below and above on every axis.
+///
+/// Positive values are solid, negative values are air. The padding lets the mesher compute
+/// gradients and border cells exactly like the neighbouring chunk does.
+#[derive(Clone)]
+pub struct PaddedDensity {
+    data: Vec<f32>,
+    dim: usize,    // CHUNK_SIZE + 4
+    height: usize, // SECTION_SIZE + 4
+}
+
+impl PaddedDensity {
+    pub const PADDING: isize = 2;
+
+    pub fn new(data: Vec<f32>, chunk_size: i32, section_size: i32) -> Self {
+        let dim = (chunk_size + 4) as usize;
+        let height = (section_size + 4) as usize;
+        assert!(data.len() == dim * dim * height);
+        Self { data, dim, height }
+    }
+
+    /// Samples `f(world_x, world_y, world_z)` over the padded extent of `coord`.
+    pub fn from_fn(coord: XYZCoords, f: impl Fn(f64, f64, f64) -> f32) -> Self {
+        let mut data = Vec::with_capacity((CHUNK_SIZE as usize + 4).pow(2) * (SECTION_SIZE as usize + 4));
+        for y in -2..=SECTION_SIZE + 1 {
+            for z in -2..=CHUNK_SIZE + 1 {
+                for x in -2..=CHUNK_SIZE + 1 {
+                    let world_pos = coord.to_world_pos(x, y, z);
+                    data.push(f(world_pos.x as f64, world_pos.y as f64, world_pos.z as f64));
+                }
+            }
+        }
+        Self::new(data, CHUNK_SIZE, SECTION_SIZE)
+    }
+
+    pub fn get(&self, x: isize, y: isize, z: isize) -> f32 {
+        // map logical coords to padded indices, clamping to the outermost sample
+        let max = self.dim as isize - 1;
+        let u = (x + Self::PADDING).clamp(0, max) as usize;
+        let v = (y + Self::PADDING).clamp(0, self.height as isize - 1) as usize;
+        let w = (z + Self::PADDING).clamp(0, max) as usize;
+        self.data[(v * self.dim + w) * self.dim + u]
+    }
+
+    pub fn set(&mut self, x: isize, y: isize, z: isize, value: f32) {
+        let max = self.dim as isize - 1;
+        let (u, v, w) = (x + Self::PADDING, y + Self::PADDING, z + Self::PADDING);
+        if (0..=max).contains(&u) && (0..self.height as isize).contains(&v) && (0..=max).contains(&w) {
+            self.data[(v as usize * self.dim + w as usize) * self.dim + u as usize] = value;
+        }
+    }
+
+    /// The corner samples owned by this section, in `density_index` order.
+    pub fn chunk_only(&self) -> Vec<f32> {
+        let mut out = Vec::with_capacity(DENSITY_DIM * DENSITY_DIM * SECTION_DIM);
+        for y in 0..SECTION_DIM as isize {
+            for z in 0..DENSITY_DIM as isize {
+                for x in 0..DENSITY_DIM as isize {
+                    out.push(self.get(x, y, z));
+                }
+            }
+        }
+        out
+    }
+}
+
+pub struct DensityGenerator {
+    heightmap: HeightmapGenerator,
+    cave_noise: Perlin,
+    overhang_noise: Perlin,
+    settings: DensitySettings,
+}
+
+impl DensityGenerator {
+    pub fn new(seed: u32) -> Self {
+        Self::with_settings(HeightmapGenerator::new(seed), seed, DensitySettings::default())
+    }
+
+    pub fn with_settings(heightmap: HeightmapGenerator, seed: u32, settings: DensitySettings) -> Self {
+        Self {
+            heightmap,
+            // offset the seeds so the 3D layers don't line up with the heightmap noise
+            cave_noise: Perlin::new(seed.wrapping_add(1)),
+            overhang_noise: Perlin::new(seed.wrapping_add(2)),
+            settings,
+        }
+    }
+
+    pub fn heightmap(&self) -> &HeightmapGenerator {
+        &self.heightmap
+    }
+
+    pub fn settings(&self) -> &DensitySettings {
+        &self.settings
+    }
+
+    /// Density at a world position, given the heightmap surface height of that column.
+    pub fn sample_density(&self, x: f64, y: f64, z: f64, surface: f32) -> f32 {
+        let s = &self.settings;
+
+        // solid below the heightmap surface, pushed in and out by 3D noise for overhangs
+        let overhang = self.overhang_noise.get([
+            x * s.overhang_frequency,
+            y * s.overhang_frequency,
+            z * s.overhang_frequency,
+        ]) as f32;
+        let surface_density = surface - y as f32 + overhang * s.overhang_strength;
+
+        // caves carve out air wherever the cave noise rises above the threshold
+        let cave = self.cave_noise.get([
+            x * s.cave_frequency,
+            y * s.cave_frequency,
+            z * s.cave_frequency,
+        ]) as f32;
+        let cave_density = (s.cave_threshold - cave) * CAVE_SCALE;
+
+        surface_density.min(cave_density)
+    }
+
+    pub fn density_at(&self, x: f64, y: f64, z: f64) -> f32 {
+        self.sample_density(x, y, z, self.heightmap.sample_height(x, z))
+    }
+
+    pub fn generate_padded_density(&self, coord: XYZCoords) -> PaddedDensity {
+        let dim = CHUNK_SIZE as usize + 4;
+
+        // the surface only depends on x/z, so sample each column once
+        let mut surface = Vec::with_capacity(dim * dim);
+        for z in -2..=CHUNK_SIZE + 1 {
+            for x in -2..=CHUNK_SIZE + 1 {
+                let world_pos = coord.to_world_pos(x, 0, z);
+                surface.push(self.heightmap.sample_height(world_pos.x as f64, world_pos.z as f64));
+            }
+        }
+
+        let mut data = Vec::with_capacity(dim * dim * (SECTION_SIZE as usize + 4));
+        for y in -2..=SECTION_SIZE + 1 {
+            for z in -2..=CHUNK_SIZE + 1 {
+                for x in -2..=CHUNK_SIZE + 1 {
+                    let world_pos = coord.to_world_pos(x, y, z);
+                    let column = (z + 2) as usize * dim + (x + 2) as usize;
+                    data.push(self.sample_density(
+                        world_pos.x as f64,
+                        world_pos.y as f64,
+                        world_pos.z as f64,
+                        surface[column],
+                    ));
+                }
+            }
+        }
+
+        PaddedDensity::new(data, CHUNK_SIZE, SECTION_SIZE)
+    }
+}
@@ -1,8 +1,17 @@
-use noise::{NoiseFn, Perlin};
-
 use crate::terrain::coords::{XZCoords, CHUNK_SIZE};
+use crate::terrain::generator::{default_pipeline, Biome, BiomeGenerator, NoiseNode, NoisePipeline, StructurePlacement};
+use crate::terrain::generator::structure::{place_in_cell, structure_cell};
+
+/// Default vertical scale: heights fall within `-HEIGHT_RANGE..=HEIGHT_RANGE`.
+pub const HEIGHT_RANGE: f32 = 32.0;
 
-const HEIGHT_RANGE: f32 = 32.0;
+/// Number of samples per edge of a stored chunk heightmap (the corners of CHUNK_SIZE cells).
+pub const HEIGHTMAP_DIM: usize = CHUNK_SIZE as usize + 1;
+
+/// Index of a local sample in a `(CHUNK_SIZE+1)^2` chunk heightmap, row-major by z.
+pub fn heightmap_index(x: usize, z: usize) -> usize {
+    z * HEIGHTMAP_DIM + x
+}
 
 #[derive(Clone)]
 pub struct PaddedHeightmap {
@@ -23,6 +32,13 @@ impl PaddedHeightmap {
         self.data[v * self.dim + u]
     }
 
+    pub fn set(&mut self, x: isize, z: isize, value: f32) {
+        let (u, v) = (x + 1, z + 1);
+        if (0..self.dim as isize).contains(&u) && (0..self.dim as isize).contains(&v) {
+            self.data[v as usize * self.dim + u as usize] = value;
+        }
+    }
+
     pub fn chunk_only(&self) -> Vec<f32> {
         // Extract the CHUNK_SIZE x CHUNK_SIZE interior from the padded (dim x dim) data
         let cs = self.dim - 2; // CHUNK_SIZE
@@ -36,24 +52,67 @@ impl PaddedHeightmap {
     }
 }
 
-pub struct HeightmapGenerator {
-    noise: Perlin,
-    base_frequency: f64,
-    octaves: usize,
-    persistence: f64,
-    lacunarity: f64,
+/// Fractal noise parameters for a `HeightmapGenerator`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct HeightmapSettings {
+    pub base_frequency: f64,
+    pub octaves: usize,
+    pub persistence: f64,
+    pub lacunarity: f64,
+    pub height_range: f32,
+    /// Frequency of the temperature/moisture noise; lower means larger biomes.
+    pub climate_frequency: f64,
 }
 
-impl HeightmapGenerator {
-    pub fn new(seed: u32) -> Self {
+impl Default for HeightmapSettings {
+    fn default() -> Self {
         Self {
-            noise: Perlin::new(seed),
             base_frequency: 0.01,
             octaves: 4,
             persistence: 0.5,
             lacunarity: 2.0,
+            height_range: HEIGHT_RANGE,
+            climate_frequency: 0.002,
         }
     }
+}
+
+pub struct HeightmapGenerator {
+    seed: u32,
+    pipeline: NoisePipeline,
+    biomes: BiomeGenerator,
+    settings: HeightmapSettings,
+}
+
+impl HeightmapGenerator {
+    pub fn new(seed: u32) -> Self {
+        Self::with_settings(seed, HeightmapSettings::default())
+    }
+
+    /// Classic fBm heights from the fractal parameters in `settings`.
+    pub fn with_settings(seed: u32, settings: HeightmapSettings) -> Self {
+        Self::with_pipeline(seed, settings, default_pipeline(&settings))
+            .expect("heightmap settings should be validated before use")
+    }
+
+    /// Heights from a custom noise pipeline; only `height_range` and `climate_frequency`
+    /// are used from `settings`.
+    pub fn with_pipeline(seed: u32, settings: HeightmapSettings, nodes: Vec<NoiseNode>) -> Result<Self, String> {
+        Ok(Self {
+            seed,
+            pipeline: NoisePipeline::new(seed, nodes)?,
+            biomes: BiomeGenerator::new(seed, settings.climate_frequency),
+            settings,
+        })
+    }
+
+    pub fn settings(&self) -> &HeightmapSettings {
+        &self.settings
+    }
+
+    pub fn biomes(&self) -> &BiomeGenerator {
+        &self.biomes
+    }
 
     pub fn generate_chunk(&self, coord: XZCoords) -> Vec<f32> {
         let mut heights = Vec::with_capacity(CHUNK_SIZE as usize * CHUNK_SIZE as usize);
@@ -87,26 +146,50 @@ impl HeightmapGenerator {
         PaddedHeightmap::new(heights, CHUNK_SIZE)
     }
 
-    fn sample_height(&self, x: f64, z: f64) -> f32 {
-        let mut amplitude = 1.0;
-        let mut frequency = self.base_frequency;
-        let mut noise_height = 0.0;
-        let mut max_value = 0.0;
-
-        for _ in 0..self.octaves {
-            let sample_x = x * frequency;
-            let sample_z = z * frequency;
-            
-            let perlin_value = self.noise.get([sample_x, sample_z]) as f64;
-            noise_height += perlin_value * amplitude;
-            
-            max_value += amplitude;
-            amplitude *= self.persistence;
-            frequency *= self.lacunarity;
+    /// Terrain height with the ground under structures flattened and blended into the
+    /// surrounding terrain.
+    pub fn sample_height(&self, x: f64, z: f64) -> f32 {
+        let natural = self.natural_height(x, z);
+        let Some(structure) = place_in_cell(self.seed, structure_cell(x, z)) else {
+            return natural;
+        };
+        let weight = structure.weight_at(x as f32, z as f32);
+        if weight <= 0.0 {
+            return natural;
         }
+        let target = self.natural_height(structure.x as f64, structure.z as f64);
+        natural + (target - natural) * weight
+    }
+
+    /// The structure in a coarse cell, if it has one, at the height its footprint is levelled to.
+    pub fn structure_in_cell(&self, cell: XZCoords) -> Option<StructurePlacement> {
+        let structure = place_in_cell(self.seed, cell)?;
+        Some(StructurePlacement { y: self.natural_height(structure.x as f64, structure.z as f64), ..structure })
+    }
+
+    /// Structures that flatten any of a chunk's padded heightmap.
+    pub fn structures_touching(&self, coord: XZCoords) -> Vec<StructurePlacement> {
+        // a chunk lies inside a single cell, and structures never reach outside their own
+        let origin = coord.to_world_pos(0, 0);
+        self.structure_in_cell(structure_cell(origin.x as f64, origin.z as f64))
+            .filter(|structure| structure.reaches_chunk(coord))
+            .into_iter()
+            .collect()
+    }
 
-        let normalized = (noise_height / max_value) as f32;
-        let height = normalized * HEIGHT_RANGE;
+    /// Height from the noise and biomes alone, before structures are levelled in.
+    fn natural_height(&self, x: f64, z: f64) -> f32 {
+        let normalized = self.pipeline.sample(x, z).clamp(-1.0, 1.0);
+
+        // every biome shapes the same noise its own way; blend them by climate so borders are smooth
+        let weights = self.biomes.weights_at(x, z);
+        let shaped: f32 = Biome::ALL.iter().zip(weights)
+            .map(|(biome, weight)| {
+                let profile = biome.profile();
+                weight * (profile.offset + profile.scale * normalized)
+            })
+            .sum();
+        let height = shaped * self.settings.height_range;
         
         // if x < 5.0 && z < 5.0 {
         //     debug!("Sampled height at ({}, {}): {} (normalized: {})", x, z, height, normalized);
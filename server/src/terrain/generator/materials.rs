@@ -0,0 +1,98 @@
+use nalgebra::Vector3;
+use noise::{NoiseFn, Perlin};
+
+use crate::entity::Mesh;
+use crate::terrain::coords::{BiomeId, MaterialId};
+use crate::terrain::generator::{Biome, BiomeGenerator, HeightmapSettings};
+use crate::terrain::material::{MaterialRule, MATERIAL_GRASS, MATERIAL_ROCK, MATERIAL_SAND, MATERIAL_SNOW};
+
+/// Frequency of the noise that jitters height bands and drives rule noise thresholds.
+const MATERIAL_NOISE_FREQUENCY: f64 = 0.05;
+/// How many blocks the noise shifts the height bands, so their borders aren't straight lines.
+const HEIGHT_JITTER: f32 = 3.0;
+
+/// Rules matching the built-in materials: sand near sea level and in deserts, rock on steep
+/// slopes, snow on peaks and in the tundra, and grass everywhere else.
+pub fn default_material_rules() -> Vec<MaterialRule> {
+    let rule = |material, min_height, max_height, min_slope, biome: Option<Biome>, priority| MaterialRule {
+        id: 0,
+        material,
+        min_height,
+        max_height,
+        min_slope,
+        max_slope: 180.0,
+        biome: biome.map(Biome::id),
+        noise_threshold: -1.0,
+        priority,
+    };
+    vec![
+        rule(MATERIAL_ROCK, f32::MIN, f32::MAX, 45.0, None, 30),
+        rule(MATERIAL_SNOW, 22.0, f32::MAX, 0.0, None, 20),
+        rule(MATERIAL_SAND, f32::MIN, f32::MAX, 0.0, Some(Biome::Desert), 15),
+        rule(MATERIAL_SNOW, f32::MIN, f32::MAX, 0.0, Some(Biome::Tundra), 15),
+        rule(MATERIAL_SAND, f32::MIN, 2.0, 0.0, None, 10),
+        rule(MATERIAL_GRASS, f32::MIN, f32::MAX, 0.0, None, 0),
+    ]
+}
+
+pub struct MaterialGenerator {
+    noise: Perlin,
+    biomes: BiomeGenerator,
+    /// Highest priority first.
+    rules: Vec<MaterialRule>,
+}
+
+impl MaterialGenerator {
+    pub fn new(seed: u32) -> Self {
+        let biomes = BiomeGenerator::new(seed, HeightmapSettings::default().climate_frequency);
+        Self::with_rules(seed, default_material_rules(), biomes)
+    }
+
+    pub fn with_rules(seed: u32, mut rules: Vec<MaterialRule>, biomes: BiomeGenerator) -> Self {
+        // highest priority first; ties go to the oldest rule so the order is stable
+        rules.sort_by_key(|rule| (std::cmp::Reverse(rule.priority), rule.id));
+        // offset the seed so the bands don't follow the heightmap noise
+        Self { noise: Perlin::new(seed.wrapping_add(3)), biomes, rules }
+    }
+
+    /// Material of a surface at a world position with the given (normalised) surface normal.
+    /// Falls back to grass if no rule matches.
+    pub fn material_at(&self, position: &Vector3<f32>, normal: &Vector3<f32>, biome: Option<BiomeId>) -> MaterialId {
+        let noise = self.noise.get([
+            position.x as f64 * MATERIAL_NOISE_FREQUENCY,
+            position.z as f64 * MATERIAL_NOISE_FREQUENCY,
+        ]) as f32;
+        let height = position.y + noise * HEIGHT_JITTER;
+        // 0 is flat ground, 90 a vertical wall and 180 a ceiling
+        let slope = normal.y.clamp(-1.0, 1.0).acos().to_degrees();
+
+        self.rules.iter()
+            .find(|rule| {
+                (rule.min_height..=rule.max_height).contains(&height)
+                    && (rule.min_slope..=rule.max_slope).contains(&slope)
+                    && rule.biome.is_none_or(|b| Some(b) == biome)
+                    && noise >= rule.noise_threshold
+            })
+            .map_or(MATERIAL_GRASS, |rule| rule.material)
+    }
+
+    /// One material per triangle of `mesh`, from the triangle's centroid, face normal and biome.
+    pub fn assign_materials(&self, mesh: &Mesh) -> Vec<MaterialId> {
+        let vertex = |i: u32| {
+            let i = i as usize * 3;
+            Vector3::new(mesh.vertices[i], mesh.vertices[i + 1], mesh.vertices[i + 2])
+        };
+
+        mesh.indices.chunks_exact(3)
+            .map(|tri| {
+                let (a, b, c) = (vertex(tri[0]), vertex(tri[1]), vertex(tri[2]));
+                let centroid = (a + b + c) / 3.0;
+                let normal = (b - a).cross(&(c - a))
+                    .try_normalize(1e-12)
+                    .unwrap_or_else(Vector3::y);
+                let biome = self.biomes.biome_at(centroid.x as f64, centroid.z as f64);
+                self.material_at(&centroid, &normal, Some(biome.id()))
+            })
+            .collect()
+    }
+}
@@ -1,10 +1,8 @@
-use std::collections::HashMap;
 use nalgebra::Vector3;
 use nalgebra::Matrix3;
 use crate::terrain::{
-    coords::{XZCoords, CHUNK_SIZE},
-    generator::PaddedHeightmap,
-    chunk::ChunkVertex,
+    coords::{XZCoords, XYZCoords, CHUNK_SIZE, SECTION_SIZE},
+    generator::{PaddedHeightmap, PaddedDensity},
 };
 use crate::entity::Mesh;
 
@@ -16,6 +14,87 @@ struct Hermite {
     n: Vector3<f32>,
 }
 
+/// Pulls density-mesh vertices toward the cell's mass point so the QEF is always solvable.
+const QEF_REGULARIZATION: f32 = 0.05;
+
+/// Corner offsets of a cell, and the 12 cell edges as pairs of corner indices.
+const CELL_CORNERS: [(isize, isize, isize); 8] = [
+    (0, 0, 0), (1, 0, 0), (0, 1, 0), (1, 1, 0),
+    (0, 0, 1), (1, 0, 1), (0, 1, 1), (1, 1, 1),
+];
+const CELL_EDGES: [(usize, usize); 12] = [
+    (0, 1), (2, 3), (4, 5), (6, 7), // along x
+    (0, 2), (1, 3), (4, 6), (5, 7), // along y
+    (0, 4), (1, 5), (2, 6), (3, 7), // along z
+];
+
+/// Levels of detail stored per chunk; level `n` keeps every `1 << n`-th height sample.
+pub const LOD_LEVELS: u8 = 4;
+/// Extra skirt depth on top of the worst height error between levels.
+const SKIRT_MARGIN: f32 = 0.5;
+
+pub fn lod_stride(lod: u8) -> usize {
+    1 << lod
+}
+
+/// Grid vertex indices along each edge of a level's grid, walked so the outside is always on
+/// the same side: -z, +x, +z, then -x.
+pub fn lod_edges(lod: u8) -> [Vec<u32>; 4] {
+    let cells = CHUNK_SIZE as usize / lod_stride(lod);
+    let index = |x: usize, z: usize| (z * (cells + 1) + x) as u32;
+    [
+        (0..=cells).map(|i| index(i, 0)).collect(),
+        (0..=cells).map(|i| index(cells, i)).collect(),
+        (0..=cells).rev().map(|i| index(i, cells)).collect(),
+        (0..=cells).rev().map(|i| index(0, i)).collect(),
+    ]
+}
+
+/// Triangles of a level's mesh: two per grid cell, row by row, then two per skirt segment,
+/// edge by edge in `lod_edges` order. Skirt vertices follow the grid vertices in the same order.
+pub fn lod_indices(lod: u8) -> Vec<u32> {
+    let cells = CHUNK_SIZE as usize / lod_stride(lod);
+    let grid = cells + 1;
+    let mut indices = Vec::with_capacity(cells * cells * 6 + cells * 4 * 6);
+    let index = |x: usize, z: usize| (z * grid + x) as u32;
+    for z in 0..cells {
+        for x in 0..cells {
+            let (a, b, c, d) = (index(x, z), index(x + 1, z), index(x + 1, z + 1), index(x, z + 1));
+            indices.extend_from_slice(&[a, c, b, a, d, c]);
+        }
+    }
+
+    let mut base = (grid * grid) as u32;
+    for top in lod_edges(lod) {
+        for i in 0..top.len() - 1 {
+            let (t0, t1) = (top[i], top[i + 1]);
+            let (b0, b1) = (base + i as u32, base + i as u32 + 1);
+            indices.extend_from_slice(&[t0, t1, b0, t1, b1, b0]);
+        }
+        base += top.len() as u32;
+    }
+    indices
+}
+
+/// How far skirts along an edge must hang so no pair of levels can open a crack there.
+/// Depends only on the edge's own samples, so both chunks sharing it agree.
+fn skirt_depth(edge: &[f32]) -> f32 {
+    let mut worst = 0.0f32;
+    for lod in 1..LOD_LEVELS {
+        let stride = lod_stride(lod);
+        for (i, h) in edge.iter().enumerate() {
+            let (i0, t) = (i / stride * stride, (i % stride) as f32 / stride as f32);
+            if t == 0.0 {
+                continue;
+            }
+            let lerp = edge[i0] + (edge[i0 + stride] - edge[i0]) * t;
+            worst = worst.max((h - lerp).abs());
+        }
+    }
+    // neighbours may use different levels, each off by up to `worst` in opposite directions
+    worst * 2.0 + SKIRT_MARGIN
+}
+
 pub struct MeshGenerator {
 }
 
@@ -24,38 +103,20 @@ impl MeshGenerator {
         Self {}
     }
 
+    /// One vertex per heightmap corner, placed by a QEF over the corner's cell.
+    ///
+    /// Each vertex depends only on the four samples of its own cell, so the vertices on a border
+    /// come out bit-for-bit the same from either chunk's padded heightmap, whatever order the
+    /// chunks are generated in.
     pub fn generate_dual_contour_mesh(
         &self,
         coord: XZCoords,
         padded: &PaddedHeightmap,
-        neighborhood: &Vec<ChunkVertex>,
     ) -> Mesh {
         // Constants
         let cs   = CHUNK_SIZE as usize;  // 32
         let grid = cs + 1;               // 33 corners per axis
     
-        // Build a cache of any border vertices already generated by neighbors
-        let mut cache: HashMap<(i32,i32),(Vector3<f32>,Vector3<f32>)> = HashMap::new();
-        for neighbor in neighborhood {
-            let base_x = neighbor.grid_x * cs as i32;
-            let base_z = neighbor.grid_z * cs as i32;
-            for nz in 0..grid {
-                for nx in 0..grid {
-                    let idx3 = (nz * grid + nx) * 3;
-                    let px = neighbor.vertices[idx3];
-                    let py = neighbor.vertices[idx3+1];
-                    let pz = neighbor.vertices[idx3+2];
-                    let nx_ = neighbor.normals[idx3];
-                    let ny_ = neighbor.normals[idx3+1];
-                    let nz_ = neighbor.normals[idx3+2];
-                    cache.insert(
-                        (base_x + nx as i32, base_z + nz as i32),
-                        (Vector3::new(px,py,pz), Vector3::new(nx_,ny_,nz_))
-                    );
-                }
-            }
-        }
-    
         // Buffers for this chunk alone
         let mut verts          = Vec::<f32>::with_capacity(grid * grid * 3);
         let mut norms          = Vec::<f32>::with_capacity(grid * grid * 3);
@@ -65,16 +126,11 @@ impl MeshGenerator {
         // PASS 1: one vertex per corner (0..=CS)
         for z in 0..=cs {
             for x in 0..=cs {
-                // world-cell coordinate
-                let gx = coord.x * cs as i32 + x as i32;
-                let gz = coord.z * cs as i32 + z as i32;
-                if let Some((pos,nrm)) = cache.get(&(gx,gz)) {
-                    verts.extend_from_slice(&[pos.x,pos.y,pos.z]);
-                    norms.extend_from_slice(&[nrm.x,nrm.y,nrm.z]);
-                    cell_vertex_idx[z][x] = Some(next_idx);
-                    next_idx += 1;
-                    continue;
-                }
+                let h00 = padded.get(x as isize,    z as isize);
+                let h10 = padded.get(x as isize+1,  z as isize);
+                let h01 = padded.get(x as isize,    z as isize+1);
+                let h11 = padded.get(x as isize+1,  z as isize+1);
+
                 // 1a) Sample four Hermite points at the vertical edges of this corner
                 let mut hermites = Vec::with_capacity(4);
                 for &(dx, dz) in &[(0,0),(1,0),(1,1),(0,1)] {
@@ -87,10 +143,7 @@ impl MeshGenerator {
                         h,
                         coord.z as f32 * cs as f32 + sz as f32,
                     );
-                    // approximate normal via finite differences:
-                    let dnx = padded.get(sx+1, sz)   - padded.get(sx-1, sz);
-                    let dnz = padded.get(sx,   sz+1) - padded.get(sx,   sz-1);
-                    let n   = Vector3::new(-dnx, 2.0, -dnz).normalize();
+                    let n = Self::cell_sample_normal(h00, h10, h01, h11, dx, dz);
                     hermites.push(Hermite { p, n });
                 }
     
@@ -120,21 +173,12 @@ impl MeshGenerator {
                 v.z = v.z.clamp(base_z,     base_z + 1.0);
     
                 // 1e) Clamp v.y between the min/max of the four corner heights
-                let h00 = padded.get(x as isize,    z as isize);
-                let h10 = padded.get(x as isize+1,  z as isize);
-                let h01 = padded.get(x as isize,    z as isize+1);
-                let h11 = padded.get(x as isize+1,  z as isize+1);
                 let min_h = h00.min(h10).min(h01).min(h11);
                 let max_h = h00.max(h10).max(h01).max(h11);
                 v.y = v.y.clamp(min_h, max_h);
     
                 // 1f) Emit vertex & averaged normal
-                let normal = hermites.iter()
-                                     .map(|h| h.n)
-                                     .fold(Vector3::zeros(), |s,n| s + n)
-                                     .normalize();
-
-                cache.insert((gx,gz), (v, normal.clone()));
+                let normal = Self::corner_normal(padded, x as isize, z as isize);
 
                 verts.extend_from_slice(&[v.x, v.y, v.z]);
                 norms.extend_from_slice(&[normal.x, normal.y, normal.z]);
@@ -167,4 +211,237 @@ impl MeshGenerator {
             materials: vec![],
         }
     }
+
+    /// A regular grid over every `lod_stride(lod)`-th height sample, with skirts hanging
+    /// off the four edges so neighbours drawn at other levels don't show cracks.
+    pub fn generate_lod_mesh(&self, coord: XZCoords, padded: &PaddedHeightmap, lod: u8) -> Mesh {
+        let cs = CHUNK_SIZE as usize;
+        let stride = lod_stride(lod);
+        let cells = cs / stride;
+        let grid = cells + 1;
+
+        let mut vertices = Vec::with_capacity(grid * grid * 3);
+        let mut normals = Vec::with_capacity(grid * grid * 3);
+        for z in 0..grid {
+            for x in 0..grid {
+                let (sx, sz) = ((x * stride) as isize, (z * stride) as isize);
+                vertices.extend_from_slice(&[
+                    (coord.x * CHUNK_SIZE) as f32 + sx as f32,
+                    padded.get(sx, sz),
+                    (coord.z * CHUNK_SIZE) as f32 + sz as f32,
+                ]);
+                // the full mesh's normal at this corner, so lighting matches across levels
+                let n = Self::corner_normal(padded, sx, sz);
+                normals.extend_from_slice(&[n.x, n.y, n.z]);
+            }
+        }
+
+        // drop a copy of each edge vertex by the edge's skirt depth, measured on every sample
+        // of the edge rather than just the ones this level keeps
+        for (edge, samples) in lod_edges(lod).iter().zip(lod_edges(0)) {
+            let heights: Vec<f32> = samples.iter()
+                .map(|&i| padded.get((i as usize % (cs + 1)) as isize, (i as usize / (cs + 1)) as isize))
+                .collect();
+            let depth = skirt_depth(&heights);
+            for &t in edge {
+                let t = t as usize * 3;
+                vertices.extend_from_slice(&[vertices[t], vertices[t + 1] - depth, vertices[t + 2]]);
+                normals.extend_from_slice(&[normals[t], normals[t + 1], normals[t + 2]]);
+            }
+        }
+
+        Mesh {
+            id: 0,
+            vertices,
+            normals,
+            indices: lod_indices(lod),
+            materials: vec![],
+        }
+    }
+
+    /// Dual contouring over a 3D density field, so caves, arches and overhangs can be meshed.
+    ///
+    /// The section owns every grid edge that starts inside it; each edge that crosses the surface
+    /// becomes a quad between the four cells around it. Cells on the low borders belong to the
+    /// neighbouring chunks and sections but are computed from the same padded samples, so borders line up.
+    pub fn generate_density_mesh(
+        &self,
+        coord: XYZCoords,
+        padded: &PaddedDensity,
+    ) -> Mesh {
+        let cs = CHUNK_SIZE as isize;
+        let ss = SECTION_SIZE as isize;
+        // cells -1..cs (-1..ss vertically) can be touched by the edges this section owns
+        let cells = (cs + 1) as usize;
+        let layers = (ss + 1) as usize;
+        let origin = Vector3::new(
+            (coord.x * CHUNK_SIZE) as f32,
+            (coord.y * SECTION_SIZE) as f32,
+            (coord.z * CHUNK_SIZE) as f32,
+        );
+
+        let mut verts = Vec::<f32>::new();
+        let mut norms = Vec::<f32>::new();
+        let mut idxs = Vec::<u32>::new();
+        let mut cell_vertex_idx: Vec<Option<u32>> = vec![None; cells * cells * layers];
+
+        for y in 0..ss {
+            for z in 0..cs {
+                for x in 0..cs {
+                    let d0 = padded.get(x, y, z);
+                    for axis in 0..3 {
+                        let (ex, ey, ez) = match axis {
+                            0 => (1, 0, 0),
+                            1 => (0, 1, 0),
+                            _ => (0, 0, 1),
+                        };
+                        let d1 = padded.get(x + ex, y + ey, z + ez);
+                        if (d0 > 0.0) == (d1 > 0.0) {
+                            continue;
+                        }
+
+                        // the four cells sharing this edge, in order around it
+                        let quad_cells = match axis {
+                            0 => [(x, y - 1, z - 1), (x, y, z - 1), (x, y, z), (x, y - 1, z)],
+                            1 => [(x - 1, y, z - 1), (x, y, z - 1), (x, y, z), (x - 1, y, z)],
+                            _ => [(x - 1, y - 1, z), (x, y - 1, z), (x, y, z), (x - 1, y, z)],
+                        };
+                        let mut quad = [0u32; 4];
+                        for (i, &(cx, cy, cz)) in quad_cells.iter().enumerate() {
+                            let slot = (((cy + 1) as usize * cells) + (cz + 1) as usize) * cells + (cx + 1) as usize;
+                            quad[i] = match cell_vertex_idx[slot] {
+                                Some(index) => index,
+                                None => {
+                                    let (v, n) = Self::solve_cell_vertex(padded, origin, cx, cy, cz);
+                                    let index = (verts.len() / 3) as u32;
+                                    verts.extend_from_slice(&[v.x, v.y, v.z]);
+                                    norms.extend_from_slice(&[n.x, n.y, n.z]);
+                                    cell_vertex_idx[slot] = Some(index);
+                                    index
+                                }
+                            };
+                        }
+
+                        // the surface faces from solid toward air along the edge
+                        let mut facing = Vector3::new(ex as f32, ey as f32, ez as f32);
+                        if d0 <= 0.0 {
+                            facing = -facing;
+                        }
+                        let [a, b, c, d] = quad;
+                        let pa = Self::vertex_at(&verts, a);
+                        let pb = Self::vertex_at(&verts, b);
+                        let pc = Self::vertex_at(&verts, c);
+                        if (pb - pa).cross(&(pc - pa)).dot(&facing) >= 0.0 {
+                            idxs.extend_from_slice(&[a, b, c, a, c, d]);
+                        } else {
+                            idxs.extend_from_slice(&[a, c, b, a, d, c]);
+                        }
+                    }
+                }
+            }
+        }
+
+        Mesh {
+            id: 0,
+            vertices: verts,
+            normals: norms,
+            indices: idxs,
+            materials: vec![],
+        }
+    }
+
+    /// Normal at one corner `(dx, dz)` of a heightmap cell, from the slope of the cell's own
+    /// edges there; central differences would reach a sample past the padding on the high borders.
+    fn cell_sample_normal(h00: f32, h10: f32, h01: f32, h11: f32, dx: isize, dz: isize) -> Vector3<f32> {
+        let dnx = if dz == 0 { h10 - h00 } else { h11 - h01 };
+        let dnz = if dx == 0 { h01 - h00 } else { h11 - h10 };
+        Vector3::new(-dnx, 1.0, -dnz).normalize()
+    }
+
+    /// Normal of the vertex at heightmap corner `(x, z)`: the average of the four sample
+    /// normals of the cell starting there. Shared by the full and LOD meshes.
+    fn corner_normal(padded: &PaddedHeightmap, x: isize, z: isize) -> Vector3<f32> {
+        let h00 = padded.get(x, z);
+        let h10 = padded.get(x + 1, z);
+        let h01 = padded.get(x, z + 1);
+        let h11 = padded.get(x + 1, z + 1);
+        [(0, 0), (1, 0), (1, 1), (0, 1)].iter()
+            .map(|&(dx, dz)| Self::cell_sample_normal(h00, h10, h01, h11, dx, dz))
+            .fold(Vector3::zeros(), |s, n| s + n)
+            .normalize()
+    }
+
+    fn vertex_at(verts: &[f32], index: u32) -> Vector3<f32> {
+        let i = index as usize * 3;
+        Vector3::new(verts[i], verts[i + 1], verts[i + 2])
+    }
+
+    /// Outward surface normal at a sample, from central differences of the density.
+    fn density_normal(padded: &PaddedDensity, x: isize, y: isize, z: isize) -> Vector3<f32> {
+        // density increases into the solid, so the outward normal is the negative gradient
+        Vector3::new(
+            padded.get(x - 1, y, z) - padded.get(x + 1, y, z),
+            padded.get(x, y - 1, z) - padded.get(x, y + 1, z),
+            padded.get(x, y, z - 1) - padded.get(x, y, z + 1),
+        )
+    }
+
+    /// Places a cell's vertex at the minimiser of its QEF, built from the Hermite samples on
+    /// every cell edge that crosses the surface.
+    fn solve_cell_vertex(
+        padded: &PaddedDensity,
+        origin: Vector3<f32>,
+        cx: isize,
+        cy: isize,
+        cz: isize,
+    ) -> (Vector3<f32>, Vector3<f32>) {
+        let mut hermites = Vec::with_capacity(CELL_EDGES.len());
+        for &(c0, c1) in &CELL_EDGES {
+            let (x0, y0, z0) = CELL_CORNERS[c0];
+            let (x1, y1, z1) = CELL_CORNERS[c1];
+            let (ax, ay, az) = (cx + x0, cy + y0, cz + z0);
+            let (bx, by, bz) = (cx + x1, cy + y1, cz + z1);
+            let da = padded.get(ax, ay, az);
+            let db = padded.get(bx, by, bz);
+            if (da > 0.0) == (db > 0.0) {
+                continue;
+            }
+            // linear estimate of where the density crosses zero along the edge
+            let t = da / (da - db);
+            // world space, so both chunks sharing a border cell solve the exact same QEF
+            let pa = origin + Vector3::new(ax as f32, ay as f32, az as f32);
+            let pb = origin + Vector3::new(bx as f32, by as f32, bz as f32);
+            let p = pa + (pb - pa) * t;
+            let n = Self::density_normal(padded, ax, ay, az) * (1.0 - t)
+                + Self::density_normal(padded, bx, by, bz) * t;
+            let n = if n.norm_squared() > 1e-12 { n.normalize() } else { Vector3::y() };
+            hermites.push(Hermite { p, n });
+        }
+
+        let mass_point = hermites.iter()
+            .map(|h| h.p)
+            .fold(Vector3::zeros(), |s, p| s + p)
+            / (hermites.len().max(1) as f32);
+
+        let mut ata = Matrix3::identity() * QEF_REGULARIZATION;
+        let mut atb = mass_point * QEF_REGULARIZATION;
+        for Hermite { p, n } in &hermites {
+            ata += n * n.transpose();
+            atb += n * n.dot(p);
+        }
+        let mut v = ata.try_inverse().map_or(mass_point, |inv| inv * atb);
+
+        // keep the vertex inside its cell
+        let base = origin + Vector3::new(cx as f32, cy as f32, cz as f32);
+        v.x = v.x.clamp(base.x, base.x + 1.0);
+        v.y = v.y.clamp(base.y, base.y + 1.0);
+        v.z = v.z.clamp(base.z, base.z + 1.0);
+
+        let normal = hermites.iter()
+            .map(|h| h.n)
+            .fold(Vector3::zeros(), |s, n| s + n);
+        let normal = if normal.norm_squared() > 1e-12 { normal.normalize() } else { Vector3::y() };
+
+        (v, normal)
+    }
 }
@@ -1,10 +1,116 @@
 mod heightmap;
+mod biome;
+mod pipeline;
+mod source;
+mod density;
 mod mesh;
+mod sculpt;
+mod materials;
+mod packed;
+mod water;
+mod props;
+mod structure;
+mod query;
 
-pub use mesh::MeshGenerator;
+/// Version of the generation code. Bump it whenever a change to noise, meshing or material
+/// code alters what gets generated, so chunks stored by older code are rebuilt.
+pub const GENERATOR_VERSION: u32 = 4;
+
+pub use mesh::{
+    MeshGenerator,
+    lod_stride,
+    lod_edges,
+    lod_indices,
+    LOD_LEVELS,
+};
+pub use source::{
+    TerrainGenerator,
+    FlatGenerator,
+};
+pub use pipeline::{
+    NoiseNode,
+    NoisePipeline,
+    FractalParams,
+    DomainWarpParams,
+    TerraceParams,
+    CurvePoint,
+    CurveParams,
+    CombineParams,
+    BlendParams,
+    ScaleBiasParams,
+    default_pipeline,
+};
+pub use biome::{
+    Biome,
+    BiomeGenerator,
+    BiomeProfile,
+};
 pub use heightmap::{
     HeightmapGenerator,
+    HeightmapSettings,
     PaddedHeightmap,
+    heightmap_index,
+    HEIGHTMAP_DIM,
+    HEIGHT_RANGE,
+};
+pub use density::{
+    DensityGenerator,
+    DensitySettings,
+    PaddedDensity,
+    density_index,
+    DENSITY_DIM,
+    SECTION_DIM,
+};
+pub use materials::{
+    MaterialGenerator,
+    default_material_rules,
+};
+pub use packed::{
+    PackedMesh,
+    PackedDensityMesh,
+    DENSITY_POSITION_STEP,
+    pack_lod_mesh,
+    unpack_lod_mesh,
+    pack_density_mesh,
+    unpack_density_mesh,
+    pack_materials,
+    encode_octahedral,
+    decode_octahedral,
+};
+pub use water::{
+    water_mask,
+    is_wet_cell,
+    is_underwater,
+    DEFAULT_SEA_LEVEL,
+    WATER_MASK_BYTES,
+};
+pub use props::{
+    Prop,
+    PropKind,
+    PropScatter,
+    PROP_SPACING,
+};
+pub use query::{
+    RayHit,
+    surface_normal,
+    raycast_heights,
+    line_of_sight_heights,
+    RAY_STEP,
+    MAX_RAY_DISTANCE,
+};
+pub use structure::{
+    Prefab,
+    StructurePlacement,
+    structure_cell,
+    STRUCTURE_CELL,
+    STRUCTURE_BLEND,
+};
+pub use sculpt::{
+    SculptMode,
+    sculpt_height,
+    sculpt_density,
+    merge_deltas,
+    apply_height_edits,
 };
 
 #[cfg(test)]
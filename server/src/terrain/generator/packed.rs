@@ -0,0 +1,189 @@
+use nalgebra::Vector3;
+use crate::terrain::{
+    coords::{MaterialId, XYZCoords, XZCoords, CHUNK_SIZE, SECTION_SIZE},
+    generator::{lod_edges, lod_indices, lod_stride},
+};
+use crate::entity::Mesh;
+
+/// A level-of-detail mesh in the compact form sent to clients. Positions and indices are
+/// implied by the grid, so only heights, normals, skirt depths and materials are kept.
+#[derive(Clone, Debug, PartialEq)]
+pub struct PackedMesh {
+    /// Height of a grid vertex is `height_min + heights[i] as f32 * height_step`.
+    pub height_min: f32,
+    pub height_step: f32,
+    /// One per grid vertex, row-major from the chunk's low corner.
+    pub heights: Vec<u16>,
+    /// Two octahedral bytes per grid vertex; skirt vertices reuse their edge vertex's normal.
+    pub normals: Vec<u8>,
+    /// How far each edge's skirt hangs, in `lod_edges` order.
+    pub skirt_depths: Vec<f32>,
+    /// Materials used by the chunk, and an index into them per triangle in `lod_indices` order.
+    pub palette: Vec<MaterialId>,
+    pub materials: Vec<u8>,
+}
+
+/// A density section mesh in the compact form sent to clients. Vertex positions are u16
+/// offsets from the cell below the section's low corner, in steps of `DENSITY_POSITION_STEP`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct PackedDensityMesh {
+    /// Three per vertex.
+    pub positions: Vec<u16>,
+    /// Two octahedral bytes per vertex.
+    pub normals: Vec<u8>,
+    /// A section has at most 33^3 cell vertices, so they fit in u16.
+    pub indices: Vec<u16>,
+    /// Materials used by the section, and an index into them per triangle.
+    pub palette: Vec<MaterialId>,
+    pub materials: Vec<u8>,
+}
+
+/// Cell vertices lie within one cell of the section, so 64 units cover them at this step.
+pub const DENSITY_POSITION_STEP: f32 = 1.0 / 1024.0;
+
+/// Maps a unit normal onto the octahedron around the y axis and quantizes it to two bytes.
+pub fn encode_octahedral(n: [f32; 3]) -> [u8; 2] {
+    let l1 = n[0].abs() + n[1].abs() + n[2].abs();
+    let (mut u, mut v) = (n[0] / l1, n[2] / l1);
+    if n[1] < 0.0 {
+        // fold the lower half over the diagonals
+        (u, v) = ((1.0 - v.abs()) * u.signum(), (1.0 - u.abs()) * v.signum());
+    }
+    let quantize = |c: f32| ((c.clamp(-1.0, 1.0) * 0.5 + 0.5) * 255.0).round() as u8;
+    [quantize(u), quantize(v)]
+}
+
+pub fn decode_octahedral(encoded: [u8; 2]) -> [f32; 3] {
+    let (mut u, mut v) = (encoded[0] as f32 / 255.0 * 2.0 - 1.0, encoded[1] as f32 / 255.0 * 2.0 - 1.0);
+    let y = 1.0 - u.abs() - v.abs();
+    if y < 0.0 {
+        (u, v) = ((1.0 - v.abs()) * u.signum(), (1.0 - u.abs()) * v.signum());
+    }
+    let n = Vector3::new(u, y, v).normalize();
+    [n.x, n.y, n.z]
+}
+
+/// Splits per-triangle materials into a palette and an index per triangle. A chunk can use
+/// at most 256 materials; any beyond that are drawn with the last one.
+pub fn pack_materials(materials: &[MaterialId]) -> (Vec<MaterialId>, Vec<u8>) {
+    let mut palette: Vec<MaterialId> = Vec::new();
+    let indices = materials.iter()
+        .map(|material| {
+            let slot = palette.iter().position(|m| m == material).unwrap_or_else(|| {
+                if palette.len() <= u8::MAX as usize {
+                    palette.push(*material);
+                }
+                palette.len() - 1
+            });
+            slot as u8
+        })
+        .collect();
+    (palette, indices)
+}
+
+/// Packs a mesh from `MeshGenerator::generate_lod_mesh`, with its materials assigned.
+pub fn pack_lod_mesh(mesh: &Mesh, lod: u8) -> PackedMesh {
+    let grid = CHUNK_SIZE as usize / lod_stride(lod) + 1;
+    let grid_vertices = grid * grid;
+
+    let heights: Vec<f32> = (0..grid_vertices).map(|i| mesh.vertices[i * 3 + 1]).collect();
+    let height_min = heights.iter().copied().fold(f32::INFINITY, f32::min);
+    let height_max = heights.iter().copied().fold(f32::NEG_INFINITY, f32::max);
+    let height_step = (height_max - height_min) / u16::MAX as f32;
+    let quantized = heights.iter()
+        .map(|h| if height_step > 0.0 { ((h - height_min) / height_step).round() as u16 } else { 0 })
+        .collect();
+
+    let normals = (0..grid_vertices)
+        .flat_map(|i| encode_octahedral([mesh.normals[i * 3], mesh.normals[i * 3 + 1], mesh.normals[i * 3 + 2]]))
+        .collect();
+
+    // each edge's skirt vertices follow the grid in order, so the first one gives its depth
+    let mut skirt = grid_vertices;
+    let skirt_depths = lod_edges(lod).iter()
+        .map(|edge| {
+            let depth = mesh.vertices[edge[0] as usize * 3 + 1] - mesh.vertices[skirt * 3 + 1];
+            skirt += edge.len();
+            depth
+        })
+        .collect();
+
+    let (palette, materials) = pack_materials(&mesh.materials);
+    PackedMesh { height_min, height_step, heights: quantized, normals, skirt_depths, palette, materials }
+}
+
+/// Rebuilds the mesh a `PackedMesh` was packed from, up to quantization.
+pub fn unpack_lod_mesh(coord: XZCoords, packed: &PackedMesh, lod: u8) -> Mesh {
+    let stride = lod_stride(lod);
+    let grid = CHUNK_SIZE as usize / stride + 1;
+
+    let mut vertices = Vec::with_capacity(grid * grid * 3);
+    let mut normals = Vec::with_capacity(grid * grid * 3);
+    for (i, &height) in packed.heights.iter().enumerate() {
+        vertices.extend_from_slice(&[
+            (coord.x * CHUNK_SIZE) as f32 + ((i % grid) * stride) as f32,
+            packed.height_min + height as f32 * packed.height_step,
+            (coord.z * CHUNK_SIZE) as f32 + ((i / grid) * stride) as f32,
+        ]);
+        normals.extend_from_slice(&decode_octahedral([packed.normals[i * 2], packed.normals[i * 2 + 1]]));
+    }
+
+    for (edge, depth) in lod_edges(lod).iter().zip(&packed.skirt_depths) {
+        for &t in edge {
+            let t = t as usize * 3;
+            vertices.extend_from_slice(&[vertices[t], vertices[t + 1] - depth, vertices[t + 2]]);
+            normals.extend_from_slice(&[normals[t], normals[t + 1], normals[t + 2]]);
+        }
+    }
+
+    Mesh {
+        id: 0,
+        vertices,
+        normals,
+        indices: lod_indices(lod),
+        materials: packed.materials.iter().map(|&i| packed.palette[i as usize]).collect(),
+    }
+}
+
+fn density_origin(coord: XYZCoords) -> [f32; 3] {
+    [
+        (coord.x * CHUNK_SIZE - 1) as f32,
+        (coord.y * SECTION_SIZE - 1) as f32,
+        (coord.z * CHUNK_SIZE - 1) as f32,
+    ]
+}
+
+/// Packs a mesh from `MeshGenerator::generate_density_mesh`, with its materials assigned.
+pub fn pack_density_mesh(coord: XYZCoords, mesh: &Mesh) -> PackedDensityMesh {
+    let origin = density_origin(coord);
+    let positions = mesh.vertices.iter().enumerate()
+        .map(|(i, v)| ((v - origin[i % 3]) / DENSITY_POSITION_STEP).round().clamp(0.0, u16::MAX as f32) as u16)
+        .collect();
+    let normals = mesh.normals.chunks_exact(3)
+        .flat_map(|n| encode_octahedral([n[0], n[1], n[2]]))
+        .collect();
+    let (palette, materials) = pack_materials(&mesh.materials);
+    PackedDensityMesh {
+        positions,
+        normals,
+        indices: mesh.indices.iter().map(|&i| i as u16).collect(),
+        palette,
+        materials,
+    }
+}
+
+/// Rebuilds the mesh a `PackedDensityMesh` was packed from, up to quantization.
+pub fn unpack_density_mesh(coord: XYZCoords, packed: &PackedDensityMesh) -> Mesh {
+    let origin = density_origin(coord);
+    Mesh {
+        id: 0,
+        vertices: packed.positions.iter().enumerate()
+            .map(|(i, &p)| origin[i % 3] + p as f32 * DENSITY_POSITION_STEP)
+            .collect(),
+        normals: packed.normals.chunks_exact(2)
+            .flat_map(|n| decode_octahedral([n[0], n[1]]))
+            .collect(),
+        indices: packed.indices.iter().map(|&i| i as u32).collect(),
+        materials: packed.materials.iter().map(|&i| packed.palette[i as usize]).collect(),
+    }
+}
@@ -0,0 +1,298 @@
+use noise::{NoiseFn, Perlin};
+use spacetimedb::SpacetimeType;
+
+use crate::terrain::generator::HeightmapSettings;
+
+const MAX_PIPELINE_NODES: usize = 64;
+const MAX_PIPELINE_OCTAVES: u32 = 16;
+
+/// Parameters shared by the fractal noise sources.
+#[derive(SpacetimeType)]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct FractalParams {
+    pub frequency: f64,
+    pub octaves: u32,
+    pub persistence: f64,
+    pub lacunarity: f64,
+    /// Added to the world seed, so several sources in one pipeline can differ.
+    pub seed_offset: u32,
+}
+
+/// Samples `source` at coordinates pushed around by `warp_x` and `warp_z`.
+#[derive(SpacetimeType)]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct DomainWarpParams {
+    pub source: u32,
+    pub warp_x: u32,
+    pub warp_z: u32,
+    /// How many blocks a warp value of 1 moves the sample.
+    pub strength: f64,
+}
+
+/// Quantises `source` into `steps` flat levels. Higher `sharpness` flattens the levels and
+/// steepens the risers between them; 1 leaves the slope unchanged.
+#[derive(SpacetimeType)]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct TerraceParams {
+    pub source: u32,
+    pub steps: u32,
+    pub sharpness: f32,
+}
+
+#[derive(SpacetimeType)]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct CurvePoint {
+    pub input: f32,
+    pub output: f32,
+}
+
+/// Remaps `source` through a piecewise-linear curve; points must be sorted by input.
+#[derive(SpacetimeType)]
+#[derive(Clone, Debug, PartialEq)]
+pub struct CurveParams {
+    pub source: u32,
+    pub points: Vec<CurvePoint>,
+}
+
+#[derive(SpacetimeType)]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct CombineParams {
+    pub a: u32,
+    pub b: u32,
+}
+
+/// Mixes `a` into `b` as `control` goes from -1 to 1.
+#[derive(SpacetimeType)]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct BlendParams {
+    pub a: u32,
+    pub b: u32,
+    pub control: u32,
+}
+
+#[derive(SpacetimeType)]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ScaleBiasParams {
+    pub source: u32,
+    pub scale: f32,
+    pub bias: f32,
+}
+
+/// One step of a height pipeline. Nodes refer to earlier nodes by their index in the list,
+/// and the last node is the pipeline's output, roughly in -1..1.
+#[derive(SpacetimeType)]
+#[derive(Clone, Debug, PartialEq)]
+pub enum NoiseNode {
+    Constant(f32),
+    Fbm(FractalParams),
+    /// Sharp ridges where the noise crosses zero, for mountain ranges.
+    Ridged(FractalParams),
+    /// Rounded bumps, for hills and dunes.
+    Billow(FractalParams),
+    DomainWarp(DomainWarpParams),
+    Terrace(TerraceParams),
+    Curve(CurveParams),
+    ScaleBias(ScaleBiasParams),
+    Min(CombineParams),
+    Max(CombineParams),
+    Blend(BlendParams),
+}
+
+impl NoiseNode {
+    fn inputs(&self) -> Vec<u32> {
+        match self {
+            NoiseNode::Constant(_) | NoiseNode::Fbm(_) | NoiseNode::Ridged(_) | NoiseNode::Billow(_) => vec![],
+            NoiseNode::DomainWarp(p) => vec![p.source, p.warp_x, p.warp_z],
+            NoiseNode::Terrace(p) => vec![p.source],
+            NoiseNode::Curve(p) => vec![p.source],
+            NoiseNode::ScaleBias(p) => vec![p.source],
+            NoiseNode::Min(p) | NoiseNode::Max(p) => vec![p.a, p.b],
+            NoiseNode::Blend(p) => vec![p.a, p.b, p.control],
+        }
+    }
+
+    /// Inputs sampled at the node's own position; a domain warp samples its source elsewhere.
+    fn inputs_in_place(&self) -> Vec<u32> {
+        match self {
+            NoiseNode::DomainWarp(p) => vec![p.warp_x, p.warp_z],
+            _ => self.inputs(),
+        }
+    }
+
+    fn fractal(&self) -> Option<&FractalParams> {
+        match self {
+            NoiseNode::Fbm(p) | NoiseNode::Ridged(p) | NoiseNode::Billow(p) => Some(p),
+            _ => None,
+        }
+    }
+}
+
+/// The single-node pipeline equivalent to the classic fBm heightmap settings.
+pub fn default_pipeline(settings: &HeightmapSettings) -> Vec<NoiseNode> {
+    vec![NoiseNode::Fbm(FractalParams {
+        frequency: settings.base_frequency,
+        octaves: settings.octaves as u32,
+        persistence: settings.persistence,
+        lacunarity: settings.lacunarity,
+        seed_offset: 0,
+    })]
+}
+
+/// A validated list of `NoiseNode`s with the Perlin sources they need.
+pub struct NoisePipeline {
+    nodes: Vec<NoiseNode>,
+    sources: Vec<Option<Perlin>>,
+    /// Per node, a bit for itself and every node it needs evaluated at the same position.
+    needs: Vec<u64>,
+}
+
+impl NoisePipeline {
+    pub fn new(seed: u32, nodes: Vec<NoiseNode>) -> Result<Self, String> {
+        Self::validate(&nodes)?;
+        let sources = nodes.iter()
+            .map(|node| node.fractal().map(|p| Perlin::new(seed.wrapping_add(p.seed_offset))))
+            .collect();
+        let mut needs: Vec<u64> = Vec::with_capacity(nodes.len());
+        for (i, node) in nodes.iter().enumerate() {
+            let inputs = node.inputs_in_place().into_iter().fold(0, |mask, input| mask | needs[input as usize]);
+            needs.push(inputs | 1 << i);
+        }
+        Ok(Self { nodes, sources, needs })
+    }
+
+    pub fn validate(nodes: &[NoiseNode]) -> Result<(), String> {
+        if nodes.is_empty() || nodes.len() > MAX_PIPELINE_NODES {
+            return Err(format!("a pipeline needs between 1 and {} nodes", MAX_PIPELINE_NODES));
+        }
+        for (i, node) in nodes.iter().enumerate() {
+            // only looking backwards keeps the graph acyclic
+            if let Some(input) = node.inputs().into_iter().find(|input| *input as usize >= i) {
+                return Err(format!("node {} refers to node {}, which does not come before it", i, input));
+            }
+            if let Some(p) = node.fractal() {
+                let positive = |v: f64| v.is_finite() && v > 0.0;
+                if !(positive(p.frequency) && positive(p.persistence) && positive(p.lacunarity)) {
+                    return Err(format!("node {} needs a finite, positive frequency, persistence and lacunarity", i));
+                }
+                if p.octaves == 0 || p.octaves > MAX_PIPELINE_OCTAVES {
+                    return Err(format!("node {} needs 1 to {} octaves", i, MAX_PIPELINE_OCTAVES));
+                }
+            }
+            match node {
+                NoiseNode::Constant(value) if !value.is_finite() => {
+                    return Err(format!("node {} needs a finite value", i));
+                }
+                NoiseNode::DomainWarp(p) if !p.strength.is_finite() => {
+                    return Err(format!("node {} needs a finite warp strength", i));
+                }
+                NoiseNode::ScaleBias(p) if !(p.scale.is_finite() && p.bias.is_finite()) => {
+                    return Err(format!("node {} needs a finite scale and bias", i));
+                }
+                NoiseNode::Terrace(p) if p.steps == 0 || !(p.sharpness.is_finite() && p.sharpness >= 1.0) => {
+                    return Err(format!("node {} needs at least one step and a finite sharpness of at least 1", i));
+                }
+                NoiseNode::Curve(p) if p.points.iter().any(|point| !(point.input.is_finite() && point.output.is_finite())) => {
+                    return Err(format!("node {} needs finite curve points", i));
+                }
+                NoiseNode::Curve(p) if p.points.len() < 2 || p.points.windows(2).any(|w| w[0].input >= w[1].input) => {
+                    return Err(format!("node {} needs at least two curve points sorted by input", i));
+                }
+                _ => {}
+            }
+        }
+        Ok(())
+    }
+
+    /// Output of the last node at a world position.
+    pub fn sample(&self, x: f64, z: f64) -> f32 {
+        self.eval(self.nodes.len() - 1, x, z)
+    }
+
+    /// Output of node `target` at a world position. Each node it needs there is evaluated once,
+    /// in index order, so nodes shared by several others don't multiply the cost.
+    fn eval(&self, target: usize, x: f64, z: f64) -> f32 {
+        let mut values = [0.0; MAX_PIPELINE_NODES];
+        let needs = self.needs[target];
+        for index in 0..=target {
+            if needs & 1 << index != 0 {
+                values[index] = self.eval_node(index, &values, x, z);
+            }
+        }
+        values[target]
+    }
+
+    /// Output of one node, given the values of the inputs it samples in place.
+    fn eval_node(&self, index: usize, values: &[f32], x: f64, z: f64) -> f32 {
+        match &self.nodes[index] {
+            NoiseNode::Constant(value) => *value,
+            NoiseNode::Fbm(p) => self.fractal(index, p, x, z, |n| n),
+            NoiseNode::Ridged(p) => {
+                // 1 - |n| peaks along the zero crossings; squaring sharpens the crests
+                let ridges = self.fractal(index, p, x, z, |n| (1.0 - n.abs()).powi(2));
+                ridges * 2.0 - 1.0
+            }
+            NoiseNode::Billow(p) => {
+                let billows = self.fractal(index, p, x, z, f64::abs);
+                billows * 2.0 - 1.0
+            }
+            NoiseNode::DomainWarp(p) => {
+                let wx = values[p.warp_x as usize] as f64 * p.strength;
+                let wz = values[p.warp_z as usize] as f64 * p.strength;
+                // the source is read somewhere else, so it needs its own evaluation
+                self.eval(p.source as usize, x + wx, z + wz)
+            }
+            NoiseNode::Terrace(p) => {
+                let steps = p.steps as f32;
+                let t = (values[p.source as usize].clamp(-1.0, 1.0) + 1.0) * 0.5 * steps;
+                let level = t.floor();
+                let terraced = level + (t - level).powf(p.sharpness);
+                terraced / steps * 2.0 - 1.0
+            }
+            NoiseNode::Curve(p) => {
+                let value = values[p.source as usize];
+                let points = &p.points;
+                let (first, last) = (points[0], points[points.len() - 1]);
+                if value <= first.input {
+                    return first.output;
+                }
+                if value >= last.input {
+                    return last.output;
+                }
+                let segment = points.windows(2)
+                    .find(|w| value <= w[1].input)
+                    .unwrap_or(&points[points.len() - 2..]);
+                let t = (value - segment[0].input) / (segment[1].input - segment[0].input);
+                segment[0].output + (segment[1].output - segment[0].output) * t
+            }
+            NoiseNode::ScaleBias(p) => values[p.source as usize] * p.scale + p.bias,
+            NoiseNode::Min(p) => values[p.a as usize].min(values[p.b as usize]),
+            NoiseNode::Max(p) => values[p.a as usize].max(values[p.b as usize]),
+            NoiseNode::Blend(p) => {
+                let t = ((values[p.control as usize] + 1.0) * 0.5).clamp(0.0, 1.0);
+                let a = values[p.a as usize];
+                let b = values[p.b as usize];
+                a + (b - a) * t
+            }
+        }
+    }
+
+    /// Sums octaves of `shape(perlin)` and normalises by the total amplitude.
+    fn fractal(&self, index: usize, p: &FractalParams, x: f64, z: f64, shape: impl Fn(f64) -> f64) -> f32 {
+        let Some(noise) = &self.sources[index] else {
+            return 0.0;
+        };
+        let mut amplitude = 1.0;
+        let mut frequency = p.frequency;
+        let mut total = 0.0;
+        let mut max_value = 0.0;
+
+        for _ in 0..p.octaves {
+            total += shape(noise.get([x * frequency, z * frequency])) * amplitude;
+            max_value += amplitude;
+            amplitude *= p.persistence;
+            frequency *= p.lacunarity;
+        }
+
+        (total / max_value) as f32
+    }
+}
@@ -0,0 +1,175 @@
+use nalgebra::Vector3;
+
+use crate::terrain::{
+    coords::{XZCoords, CHUNK_SIZE},
+    generator::{Biome, BiomeGenerator, PaddedHeightmap},
+};
+
+/// Closest two props may stand, in blocks.
+pub const PROP_SPACING: f32 = 4.0;
+/// Candidate points thrown per chunk before filtering.
+const SCATTER_ATTEMPTS: usize = 96;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[repr(u32)]
+pub enum PropKind {
+    Tree = 0,
+    Bush = 1,
+    Rock = 2,
+}
+
+impl PropKind {
+    pub const ALL: [PropKind; 3] = [PropKind::Tree, PropKind::Bush, PropKind::Rock];
+
+    pub fn id(self) -> u32 {
+        self as u32
+    }
+
+    pub fn from_id(id: u32) -> Option<Self> {
+        Self::ALL.into_iter().find(|kind| kind.id() == id)
+    }
+
+    /// Steepest ground, in degrees, and highest ground the prop grows on.
+    fn limits(self) -> (f32, f32) {
+        match self {
+            PropKind::Tree => (30.0, 22.0),
+            PropKind::Bush => (35.0, 24.0),
+            PropKind::Rock => (55.0, f32::MAX),
+        }
+    }
+}
+
+/// How much of a biome is covered, as the chance a candidate point is kept, and the relative
+/// weights of trees, bushes and rocks there.
+fn biome_props(biome: Biome) -> (f32, [f32; 3]) {
+    match biome {
+        Biome::Plains => (0.5, [0.5, 0.35, 0.15]),
+        Biome::Desert => (0.15, [0.0, 0.3, 0.7]),
+        Biome::Mountains => (0.35, [0.25, 0.1, 0.65]),
+        Biome::Tundra => (0.2, [0.2, 0.1, 0.7]),
+        Biome::Swamp => (0.7, [0.55, 0.4, 0.05]),
+    }
+}
+
+/// A placed prop, in world space.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Prop {
+    pub kind: PropKind,
+    pub x: f32,
+    pub y: f32,
+    pub z: f32,
+    /// Turn about the vertical axis, in radians.
+    pub rotation: f32,
+    pub scale: f32,
+}
+
+/// SplitMix64, so scatters only depend on the seed and never on the platform.
+pub(super) struct ScatterRng(u64);
+
+impl ScatterRng {
+    pub(super) fn new(seed: u32, coord: XZCoords) -> Self {
+        let x = (coord.x as u32 as u64).wrapping_mul(0x9e37_79b9_7f4a_7c15);
+        let z = (coord.z as u32 as u64).wrapping_mul(0xc2b2_ae3d_27d4_eb4f);
+        Self(((seed as u64) << 32) ^ x ^ z)
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    /// Uniform in 0..1.
+    pub(super) fn next_f32(&mut self) -> f32 {
+        (self.next_u64() >> 40) as f32 / (1u64 << 24) as f32
+    }
+}
+
+/// Deterministic Poisson-disk scatter of trees, bushes and rocks over a chunk.
+pub struct PropScatter {
+    seed: u32,
+    biomes: BiomeGenerator,
+    sea_level: f32,
+}
+
+impl PropScatter {
+    pub fn new(seed: u32, biomes: BiomeGenerator, sea_level: f32) -> Self {
+        Self { seed, biomes, sea_level }
+    }
+
+    /// Props for a chunk, seeded from the world seed and the chunk's coordinates. Points keep
+    /// half the spacing away from the chunk's edges, so neighbouring chunks never crowd each
+    /// other and can be scattered in any order.
+    pub fn scatter(&self, coord: XZCoords, padded: &PaddedHeightmap) -> Vec<Prop> {
+        let mut rng = ScatterRng::new(self.seed, coord);
+        let margin = PROP_SPACING / 2.0;
+        let span = CHUNK_SIZE as f32 - PROP_SPACING;
+        let origin = coord.to_world_pos(0, 0);
+
+        let mut points: Vec<(f32, f32)> = Vec::new();
+        let mut props = Vec::new();
+        for _ in 0..SCATTER_ATTEMPTS {
+            // draw every value up front so a rejected point doesn't shift the ones after it
+            let (lx, lz) = (margin + rng.next_f32() * span, margin + rng.next_f32() * span);
+            let (keep, pick, rotation, scale) = (rng.next_f32(), rng.next_f32(), rng.next_f32(), rng.next_f32());
+
+            let spaced = points.iter()
+                .all(|&(px, pz)| (px - lx).powi(2) + (pz - lz).powi(2) >= PROP_SPACING * PROP_SPACING);
+            if !spaced {
+                continue;
+            }
+            points.push((lx, lz));
+
+            let (x, z) = (origin.x + lx, origin.z + lz);
+            let (density, weights) = biome_props(self.biomes.biome_at(x as f64, z as f64));
+            let (height, slope) = surface_at(padded, lx, lz);
+            if keep >= density || height <= self.sea_level {
+                continue;
+            }
+
+            let allowed = |kind: &PropKind| {
+                let (max_slope, max_height) = kind.limits();
+                slope <= max_slope && height <= max_height
+            };
+            let total: f32 = PropKind::ALL.iter().filter(|k| allowed(k)).map(|k| weights[k.id() as usize]).sum();
+            if total <= 0.0 {
+                continue;
+            }
+            let mut remaining = pick * total;
+            let kind = PropKind::ALL.into_iter()
+                .filter(allowed)
+                .find(|k| {
+                    remaining -= weights[k.id() as usize];
+                    remaining < 0.0
+                })
+                .unwrap_or(PropKind::Rock);
+
+            props.push(Prop {
+                kind,
+                x,
+                y: height,
+                z,
+                rotation: rotation * std::f32::consts::TAU,
+                scale: 0.75 + scale * 0.5,
+            });
+        }
+        props
+    }
+}
+
+/// Interpolated height and slope in degrees at a local position inside the chunk.
+fn surface_at(padded: &PaddedHeightmap, lx: f32, lz: f32) -> (f32, f32) {
+    let (x0, z0) = (lx.floor() as isize, lz.floor() as isize);
+    let (tx, tz) = (lx - x0 as f32, lz - z0 as f32);
+    let near = padded.get(x0, z0) + (padded.get(x0 + 1, z0) - padded.get(x0, z0)) * tx;
+    let far = padded.get(x0, z0 + 1) + (padded.get(x0 + 1, z0 + 1) - padded.get(x0, z0 + 1)) * tx;
+    let height = near + (far - near) * tz;
+
+    // the cell's own edge slopes, as the surface mesh uses
+    let dnx = (padded.get(x0 + 1, z0) - padded.get(x0, z0) + padded.get(x0 + 1, z0 + 1) - padded.get(x0, z0 + 1)) / 2.0;
+    let dnz = (padded.get(x0, z0 + 1) - padded.get(x0, z0) + padded.get(x0 + 1, z0 + 1) - padded.get(x0 + 1, z0)) / 2.0;
+    let normal = Vector3::new(-dnx, 1.0, -dnz).normalize();
+    (height, normal.y.acos().to_degrees())
+}
@@ -0,0 +1,83 @@
+use nalgebra::Vector3;
+
+/// Distance between samples when marching a ray over the terrain. Heights are bilinear between
+/// whole-block corners, so a quarter block can't step over a crest by more than a sliver.
+pub const RAY_STEP: f32 = 0.25;
+/// Longest ray marched; longer requests are cut to it, since every `RAY_STEP` costs a lookup.
+pub const MAX_RAY_DISTANCE: f32 = 1024.0;
+/// Bisections refining a hit once the march has crossed the surface.
+const RAY_REFINE_STEPS: usize = 12;
+/// Half the span of the central differences `surface_normal` takes.
+const NORMAL_EPSILON: f32 = 0.5;
+
+/// Where a ray met the terrain.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct RayHit {
+    pub point: Vector3<f32>,
+    /// Distance along the ray from its origin.
+    pub distance: f32,
+    pub normal: Vector3<f32>,
+}
+
+/// Upward unit normal of a height field at a world column.
+pub fn surface_normal(height: impl Fn(f32, f32) -> f32, x: f32, z: f32) -> Vector3<f32> {
+    let dx = height(x + NORMAL_EPSILON, z) - height(x - NORMAL_EPSILON, z);
+    let dz = height(x, z + NORMAL_EPSILON) - height(x, z - NORMAL_EPSILON);
+    Vector3::new(-dx, 2.0 * NORMAL_EPSILON, -dz).normalize()
+}
+
+/// First point within `max_distance`, at most `MAX_RAY_DISTANCE`, where a ray passes below a
+/// height field. A ray starting underground hits at its origin; a non-finite ray hits nothing.
+pub fn raycast_heights(
+    height: impl Fn(f32, f32) -> f32,
+    origin: Vector3<f32>,
+    direction: Vector3<f32>,
+    max_distance: f32,
+) -> Option<RayHit> {
+    let finite = |v: Vector3<f32>| v.iter().all(|c| c.is_finite());
+    if !(finite(origin) && finite(direction) && max_distance.is_finite()) {
+        return None;
+    }
+    let max_distance = max_distance.min(MAX_RAY_DISTANCE);
+    let direction = direction.try_normalize(f32::EPSILON)?;
+    let above = |t: f32| {
+        let p = origin + direction * t;
+        p.y - height(p.x, p.z)
+    };
+    let hit = |t: f32| {
+        let point = origin + direction * t;
+        RayHit { point, distance: t, normal: surface_normal(&height, point.x, point.z) }
+    };
+
+    if above(0.0) <= 0.0 {
+        return Some(hit(0.0));
+    }
+    let mut previous = 0.0;
+    while previous < max_distance {
+        let t = (previous + RAY_STEP).min(max_distance);
+        if above(t) <= 0.0 {
+            // the surface is between the last two samples
+            let (mut lo, mut hi) = (previous, t);
+            for _ in 0..RAY_REFINE_STEPS {
+                let mid = (lo + hi) / 2.0;
+                if above(mid) <= 0.0 {
+                    hi = mid;
+                } else {
+                    lo = mid;
+                }
+            }
+            return Some(hit(hi));
+        }
+        previous = t;
+    }
+    None
+}
+
+/// Whether the straight line between two points stays above a height field.
+pub fn line_of_sight_heights(height: impl Fn(f32, f32) -> f32, from: Vector3<f32>, to: Vector3<f32>) -> bool {
+    let distance = (to - from).norm();
+    if distance <= f32::EPSILON {
+        return from.y > height(from.x, from.z);
+    }
+    raycast_heights(height, from, to - from, distance).is_none()
+}
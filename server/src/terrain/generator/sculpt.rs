@@ -0,0 +1,87 @@
+use std::collections::{BTreeMap, HashSet};
+
+use crate::terrain::coords::{DensityDelta, Vec3, XZCoords, CHUNK_SIZE};
+use crate::terrain::generator::{PaddedHeightmap, HEIGHTMAP_DIM};
+
+/// Deltas smaller than this are treated as no change.
+const DELTA_EPSILON: f32 = 1e-4;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SculptMode {
+    Dig,
+    Fill,
+}
+
+/// New surface height of a heightmap column after a sphere edit, or `None` if the sphere
+/// doesn't reach the surface of this column (it's buried, floating, or misses it entirely).
+pub fn sculpt_height(mode: SculptMode, height: f32, center: &Vec3, radius: f32, x: f32, z: f32) -> Option<f32> {
+    let dx = x - center.x;
+    let dz = z - center.z;
+    let half_span_sq = radius * radius - dx * dx - dz * dz;
+    if half_span_sq <= 0.0 {
+        return None;
+    }
+    let half_span = half_span_sq.sqrt();
+    let (bottom, top) = (center.y - half_span, center.y + half_span);
+
+    let new_height = match mode {
+        SculptMode::Dig if height > bottom && height <= top => bottom,
+        SculptMode::Fill if height >= bottom && height < top => top,
+        _ => return None,
+    };
+    ((new_height - height).abs() > DELTA_EPSILON).then_some(new_height)
+}
+
+/// New density of a sample after a sphere edit (positive is solid).
+pub fn sculpt_density(mode: SculptMode, density: f32, center: &Vec3, radius: f32, x: f32, y: f32, z: f32) -> f32 {
+    let offset = Vec3::new(x - center.x, y - center.y, z - center.z);
+    let inside = radius - offset.length();
+    match mode {
+        SculptMode::Dig => density.min(-inside),
+        SculptMode::Fill => density.max(inside),
+    }
+}
+
+/// Adds `new` deltas into `existing`, summing values that target the same index.
+/// The result is sorted by index and drops entries that cancel out.
+pub fn merge_deltas(existing: &mut Vec<DensityDelta>, new: &[DensityDelta]) {
+    let mut merged: BTreeMap<u32, f32> = BTreeMap::new();
+    for delta in existing.iter().chain(new) {
+        *merged.entry(delta.index).or_insert(0.0) += delta.value;
+    }
+    *existing = merged.into_iter()
+        .filter(|(_, value)| value.abs() > DELTA_EPSILON)
+        .map(|(index, value)| DensityDelta { index, value })
+        .collect();
+}
+
+/// Adds the edits stored for a chunk and its eight neighbours to the chunk's padded heightmap.
+/// `edits_at` returns the deltas stored for one chunk, if any.
+pub fn apply_height_edits(
+    padded: &mut PaddedHeightmap,
+    coord: XZCoords,
+    edits_at: impl Fn(XZCoords) -> Option<Vec<DensityDelta>>,
+) {
+    let dim = HEIGHTMAP_DIM as i32;
+
+    // border samples are recorded in every chunk that shares them, so apply each one once
+    let mut applied = HashSet::new();
+    for dz in -1..=1 {
+        for dx in -1..=1 {
+            let Some(deltas) = edits_at(XZCoords { x: coord.x + dx, z: coord.z + dz }) else {
+                continue;
+            };
+            for delta in &deltas {
+                let x = delta.index as i32 % dim + dx * CHUNK_SIZE;
+                let z = delta.index as i32 / dim + dz * CHUNK_SIZE;
+                if !(-1..=CHUNK_SIZE + 1).contains(&x) || !(-1..=CHUNK_SIZE + 1).contains(&z) {
+                    continue;
+                }
+                if applied.insert((x, z)) {
+                    let (x, z) = (x as isize, z as isize);
+                    padded.set(x, z, padded.get(x, z) + delta.value);
+                }
+            }
+        }
+    }
+}
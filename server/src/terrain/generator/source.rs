@@ -0,0 +1,102 @@
+use std::ops::RangeInclusive;
+
+use crate::terrain::coords::{section_of, XYZCoords, XZCoords, CHUNK_SIZE};
+use crate::terrain::generator::{DensityGenerator, PaddedDensity, PaddedHeightmap, StructurePlacement};
+
+/// Where a world's terrain comes from. Chunk reducers only talk to this trait, so flat test
+/// worlds, procedural worlds and imported heightmaps are interchangeable.
+pub trait TerrainGenerator {
+    /// Surface height of a world column.
+    fn sample_height(&self, x: f64, z: f64) -> f32;
+
+    /// Density at a world position; positive is solid. Defaults to solid below the surface.
+    fn density_at(&self, x: f64, y: f64, z: f64) -> f32 {
+        self.sample_height(x, z) - y as f32
+    }
+
+    /// Corner heights of a chunk with one sample of padding on every side.
+    fn generate_padded_heightmap(&self, coord: XZCoords) -> PaddedHeightmap {
+        let mut heights = Vec::with_capacity((CHUNK_SIZE as usize + 3).pow(2));
+        for z in -1..=CHUNK_SIZE + 1 {
+            for x in -1..=CHUNK_SIZE + 1 {
+                let world_pos = coord.to_world_pos(x, z);
+                heights.push(self.sample_height(world_pos.x as f64, world_pos.z as f64));
+            }
+        }
+        PaddedHeightmap::new(heights, CHUNK_SIZE)
+    }
+
+    fn generate_padded_density(&self, coord: XYZCoords) -> PaddedDensity {
+        PaddedDensity::from_fn(coord, |x, y, z| self.density_at(x, y, z))
+    }
+
+    /// How far above or below `sample_height` the density surface can be pushed, e.g. by overhangs.
+    fn surface_reach(&self) -> f32 {
+        0.0
+    }
+
+    /// Vertical sections of a chunk column that can contain its surface.
+    /// Sections above are always air and sections below only hold caves.
+    fn surface_sections(&self, column: XZCoords) -> RangeInclusive<i32> {
+        let (mut min, mut max) = (f32::MAX, f32::MIN);
+        for z in -2..=CHUNK_SIZE + 1 {
+            for x in -2..=CHUNK_SIZE + 1 {
+                let world_pos = column.to_world_pos(x, z);
+                let height = self.sample_height(world_pos.x as f64, world_pos.z as f64);
+                min = min.min(height);
+                max = max.max(height);
+            }
+        }
+        // the mesher reads two samples past each end of a section, so pad by that as well
+        let reach = self.surface_reach() + PaddedDensity::PADDING as f32;
+        section_of(min - reach)..=section_of(max + reach)
+    }
+
+    /// Structures whose flattened ground reaches a chunk. Sources without structures have none.
+    fn structures_touching(&self, _coord: XZCoords) -> Vec<StructurePlacement> {
+        Vec::new()
+    }
+}
+
+/// A flat plane at a fixed height, for tests and demo realms.
+pub struct FlatGenerator {
+    pub height: f32,
+}
+
+impl TerrainGenerator for FlatGenerator {
+    fn sample_height(&self, _x: f64, _z: f64) -> f32 {
+        self.height
+    }
+
+    fn surface_sections(&self, _column: XZCoords) -> RangeInclusive<i32> {
+        let reach = PaddedDensity::PADDING as f32;
+        section_of(self.height - reach)..=section_of(self.height + reach)
+    }
+}
+
+/// The procedural world: biome-blended Perlin heights with caves and overhangs.
+impl TerrainGenerator for DensityGenerator {
+    fn sample_height(&self, x: f64, z: f64) -> f32 {
+        self.heightmap().sample_height(x, z)
+    }
+
+    fn density_at(&self, x: f64, y: f64, z: f64) -> f32 {
+        DensityGenerator::density_at(self, x, y, z)
+    }
+
+    fn generate_padded_heightmap(&self, coord: XZCoords) -> PaddedHeightmap {
+        self.heightmap().generate_padded_heightmap(coord)
+    }
+
+    fn generate_padded_density(&self, coord: XYZCoords) -> PaddedDensity {
+        DensityGenerator::generate_padded_density(self, coord)
+    }
+
+    fn surface_reach(&self) -> f32 {
+        self.settings().overhang_strength
+    }
+
+    fn structures_touching(&self, coord: XZCoords) -> Vec<StructurePlacement> {
+        self.heightmap().structures_touching(coord)
+    }
+}
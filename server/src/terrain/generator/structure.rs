@@ -0,0 +1,116 @@
+use crate::terrain::{
+    coords::{XZCoords, CHUNK_SIZE},
+    generator::props::ScatterRng,
+};
+
+/// Side of the coarse grid cells structures are placed on, in blocks. Each cell holds at most
+/// one structure, and a multiple of the chunk size keeps every chunk inside a single cell.
+pub const STRUCTURE_CELL: i32 = 16 * CHUNK_SIZE;
+/// Chance that a cell has a structure.
+const STRUCTURE_CHANCE: f32 = 0.5;
+/// How far past its footprint a structure's flattened ground blends back into the terrain.
+pub const STRUCTURE_BLEND: f32 = 16.0;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[repr(u32)]
+pub enum Prefab {
+    Ruins = 0,
+    Camp = 1,
+    Tower = 2,
+}
+
+impl Prefab {
+    pub const ALL: [Prefab; 3] = [Prefab::Ruins, Prefab::Camp, Prefab::Tower];
+
+    pub fn id(self) -> u32 {
+        self as u32
+    }
+
+    pub fn from_id(id: u32) -> Option<Self> {
+        Self::ALL.into_iter().find(|prefab| prefab.id() == id)
+    }
+
+    /// Half the width and depth of the flattened footprint.
+    pub fn half_extents(self) -> (f32, f32) {
+        match self {
+            Prefab::Ruins => (36.0, 28.0),
+            Prefab::Camp => (20.0, 20.0),
+            Prefab::Tower => (8.0, 8.0),
+        }
+    }
+}
+
+/// Farthest any structure's influence reaches from its centre along either axis. Centres keep
+/// this far from their cell's edges, so a structure never affects terrain outside its cell.
+const STRUCTURE_REACH: f32 = 36.0 + STRUCTURE_BLEND;
+
+/// A structure placed by the generator: a flat, axis-aligned footprint at height `y`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct StructurePlacement {
+    /// Coarse grid cell the structure belongs to; unique per structure.
+    pub cell: XZCoords,
+    pub prefab: Prefab,
+    pub x: f32,
+    pub y: f32,
+    pub z: f32,
+    pub half_width: f32,
+    pub half_depth: f32,
+}
+
+impl StructurePlacement {
+    /// How strongly the footprint pulls a column to its height: 1 inside it, easing to 0 at
+    /// `STRUCTURE_BLEND` past its edge.
+    pub fn weight_at(&self, x: f32, z: f32) -> f32 {
+        let dx = ((x - self.x).abs() - self.half_width).max(0.0);
+        let dz = ((z - self.z).abs() - self.half_depth).max(0.0);
+        let t = 1.0 - ((dx * dx + dz * dz).sqrt() / STRUCTURE_BLEND).min(1.0);
+        t * t * (3.0 - 2.0 * t)
+    }
+
+    /// Whether a world column is inside the footprint itself.
+    pub fn contains(&self, x: f32, z: f32) -> bool {
+        (x - self.x).abs() <= self.half_width && (z - self.z).abs() <= self.half_depth
+    }
+
+    /// Whether the structure's influence reaches any sample of a chunk's padded heightmap.
+    pub fn reaches_chunk(&self, coord: XZCoords) -> bool {
+        let (min_x, min_z) = ((coord.x * CHUNK_SIZE - 1) as f32, (coord.z * CHUNK_SIZE - 1) as f32);
+        let (max_x, max_z) = (min_x + (CHUNK_SIZE + 2) as f32, min_z + (CHUNK_SIZE + 2) as f32);
+        let reach_x = self.half_width + STRUCTURE_BLEND;
+        let reach_z = self.half_depth + STRUCTURE_BLEND;
+        self.x + reach_x > min_x && self.x - reach_x < max_x
+            && self.z + reach_z > min_z && self.z - reach_z < max_z
+    }
+}
+
+/// The coarse cell containing a world column.
+pub fn structure_cell(x: f64, z: f64) -> XZCoords {
+    XZCoords {
+        x: (x / STRUCTURE_CELL as f64).floor() as i32,
+        z: (z / STRUCTURE_CELL as f64).floor() as i32,
+    }
+}
+
+/// The structure in a cell, if it has one. Only depends on the seed and the cell, so every
+/// chunk agrees on it however late it is generated. `y` is left at 0 for the heightmap to fill
+/// in, since it depends on the terrain.
+pub(super) fn place_in_cell(seed: u32, cell: XZCoords) -> Option<StructurePlacement> {
+    // offset the seed so structures don't follow the prop scatter
+    let mut rng = ScatterRng::new(seed.wrapping_add(6), cell);
+    let (chance, pick, u, v) = (rng.next_f32(), rng.next_f32(), rng.next_f32(), rng.next_f32());
+    if chance >= STRUCTURE_CHANCE {
+        return None;
+    }
+    let prefab = Prefab::ALL[((pick * Prefab::ALL.len() as f32) as usize).min(Prefab::ALL.len() - 1)];
+    let (half_width, half_depth) = prefab.half_extents();
+    let span = STRUCTURE_CELL as f32 - 2.0 * STRUCTURE_REACH;
+    Some(StructurePlacement {
+        cell,
+        prefab,
+        x: (cell.x * STRUCTURE_CELL) as f32 + STRUCTURE_REACH + u * span,
+        y: 0.0,
+        z: (cell.z * STRUCTURE_CELL) as f32 + STRUCTURE_REACH + v * span,
+        half_width,
+        half_depth,
+    })
+}
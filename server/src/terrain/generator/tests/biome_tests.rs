@@ -0,0 +1,81 @@
+use crate::terrain::{
+    biome::ChunkBiome,
+    coords::{XZCoords, CHUNK_SIZE},
+    generator::{Biome, BiomeGenerator, HeightmapGenerator},
+};
+use approx::assert_relative_eq;
+use std::collections::HashSet;
+
+#[test]
+fn test_biome_ids_round_trip() {
+    for biome in Biome::ALL {
+        assert_eq!(Biome::from_id(biome.id()), Some(biome));
+    }
+    assert_eq!(Biome::from_id(99), None);
+}
+
+#[test]
+fn test_profiles_stay_within_height_range() {
+    for biome in Biome::ALL {
+        let profile = biome.profile();
+        assert!(profile.offset.abs() + profile.scale <= 1.0, "{:?} profile leaves the height range", biome);
+    }
+}
+
+#[test]
+fn test_biome_weights_are_normalised() {
+    let biomes = BiomeGenerator::new(42, 0.002);
+    for i in 0..50 {
+        let (x, z) = (i as f64 * 97.0, i as f64 * -61.0);
+        let weights = biomes.weights_at(x, z);
+        assert_relative_eq!(weights.iter().sum::<f32>(), 1.0, epsilon = 1e-5);
+
+        // the dominant weight belongs to the nearest biome
+        let strongest = weights.iter().enumerate().max_by(|a, b| a.1.total_cmp(b.1)).unwrap().0;
+        assert_eq!(Biome::ALL[strongest], biomes.biome_at(x, z));
+    }
+}
+
+#[test]
+fn test_world_has_several_biomes() {
+    let biomes = BiomeGenerator::new(42, 0.002);
+    let mut seen = HashSet::new();
+    for z in -20..20 {
+        for x in -20..20 {
+            seen.insert(biomes.biome_at(x as f64 * 100.0, z as f64 * 100.0));
+        }
+    }
+    assert!(seen.len() >= 3, "Expected a variety of biomes, got {:?}", seen);
+}
+
+#[test]
+fn test_blended_heights_are_smooth() {
+    // neighbouring samples never jump, even across biome borders
+    let generator = HeightmapGenerator::new(42);
+    for chunk in -8..8 {
+        let heights = generator.generate_chunk(XZCoords { x: chunk * 7, z: chunk * 5 });
+        for row in heights.chunks(CHUNK_SIZE as usize) {
+            for pair in row.windows(2) {
+                assert!((pair[0] - pair[1]).abs() < 4.0, "Height jumped from {} to {}", pair[0], pair[1]);
+            }
+        }
+    }
+}
+
+#[test]
+fn test_chunk_biome_regenerates_with_new_seed() {
+    let coords: Vec<XZCoords> = (-5..5).flat_map(|z| (-5..5).map(move |x| XZCoords { x: x * 9, z: z * 9 })).collect();
+    let stored: Vec<ChunkBiome> = coords.iter().map(|&c| ChunkBiome::generate(c, &BiomeGenerator::new(42, 0.002))).collect();
+
+    // the same seed leaves every stored row alone
+    for (&coord, row) in coords.iter().zip(&stored) {
+        assert!(row.same_climate(&ChunkBiome::generate(coord, &BiomeGenerator::new(42, 0.002))));
+    }
+
+    // a new seed replaces them
+    let reseeded = BiomeGenerator::new(7, 0.002);
+    let changed = coords.iter().zip(&stored)
+        .filter(|&(&coord, row)| !row.same_climate(&ChunkBiome::generate(coord, &reseeded)))
+        .count();
+    assert_eq!(changed, coords.len(), "only {} of {} chunks changed climate", changed, coords.len());
+}
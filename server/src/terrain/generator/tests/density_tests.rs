@@ -0,0 +1,216 @@
+use std::collections::HashMap;
+
+use crate::terrain::{
+    coords::{section_of, XYZCoords, XZCoords, CHUNK_SIZE, SECTION_SIZE},
+    generator::{DensityGenerator, HeightmapGenerator, MeshGenerator, TerrainGenerator, PaddedDensity, density_index, DENSITY_DIM, SECTION_DIM},
+};
+use crate::entity::Mesh;
+use nalgebra::Vector3;
+
+fn sphere(cx: f64, cy: f64, cz: f64, r: f64) -> impl Fn(f64, f64, f64) -> f32 {
+    move |x, y, z| {
+        let d = ((x - cx).powi(2) + (y - cy).powi(2) + (z - cz).powi(2)).sqrt();
+        (r - d) as f32
+    }
+}
+
+fn vertex(mesh: &Mesh, i: u32) -> Vector3<f32> {
+    let i = i as usize * 3;
+    Vector3::new(mesh.vertices[i], mesh.vertices[i + 1], mesh.vertices[i + 2])
+}
+
+// Welds identical positions across meshes and counts how many triangles use each edge
+fn edge_use_counts(meshes: &[Mesh]) -> HashMap<(u32, u32), usize> {
+    let mut welded: HashMap<[u32; 3], u32> = HashMap::new();
+    let mut counts = HashMap::new();
+    for mesh in meshes {
+        let ids: Vec<u32> = mesh.vertices.chunks_exact(3)
+            .map(|v| {
+                let key = [v[0].to_bits(), v[1].to_bits(), v[2].to_bits()];
+                let next = welded.len() as u32;
+                *welded.entry(key).or_insert(next)
+            })
+            .collect();
+        for tri in mesh.indices.chunks_exact(3) {
+            for (a, b) in [(tri[0], tri[1]), (tri[1], tri[2]), (tri[2], tri[0])] {
+                let (a, b) = (ids[a as usize], ids[b as usize]);
+                *counts.entry((a.min(b), a.max(b))).or_insert(0) += 1;
+            }
+        }
+    }
+    counts
+}
+
+#[test]
+fn test_padded_density_dimensions() {
+    let generator = DensityGenerator::new(42);
+    let padded = generator.generate_padded_density(XYZCoords { x: 0, y: 0, z: 0 });
+
+    assert_eq!(padded.chunk_only().len(), DENSITY_DIM * DENSITY_DIM * SECTION_DIM);
+}
+
+#[test]
+fn test_density_index_order() {
+    let coord = XYZCoords { x: 0, y: 0, z: 0 };
+    let padded = PaddedDensity::from_fn(coord, |x, y, z| (x + 100.0 * y + 10000.0 * z) as f32);
+    let samples = padded.chunk_only();
+
+    assert_eq!(samples[density_index(3, 5, 7)], 3.0 + 500.0 + 70000.0);
+}
+
+#[test]
+fn test_density_seed_determinism() {
+    let coord = XYZCoords { x: 1, y: -1, z: 2 };
+    let densities1 = DensityGenerator::new(7).generate_padded_density(coord).chunk_only();
+    let densities2 = DensityGenerator::new(7).generate_padded_density(coord).chunk_only();
+
+    assert_eq!(densities1, densities2, "Same seed should produce identical densities");
+}
+
+#[test]
+fn test_density_solid_below_air_above() {
+    let generator = DensityGenerator::new(42);
+
+    // far above the highest possible surface is always air
+    assert!(generator.density_at(10.0, 200.0, 10.0) < 0.0);
+    // the surface sits between two samples that straddle it
+    let mut crossings = 0;
+    for x in 0..16 {
+        let top = generator.density_at(x as f64, 100.0, 0.0);
+        let bottom = generator.density_at(x as f64, -100.0, 0.0);
+        if top < 0.0 && bottom > 0.0 {
+            crossings += 1;
+        }
+    }
+    assert!(crossings > 0, "Columns should be solid deep down and air high up");
+}
+
+#[test]
+fn test_density_continuity_across_chunks() {
+    let generator = DensityGenerator::new(42);
+    let a = generator.generate_padded_density(XYZCoords { x: 0, y: 0, z: 0 }).chunk_only();
+    let b = generator.generate_padded_density(XYZCoords { x: 1, y: 0, z: 0 }).chunk_only();
+    let cs = CHUNK_SIZE as usize;
+
+    for y in 0..SECTION_DIM {
+        for z in 0..DENSITY_DIM {
+            assert_eq!(a[density_index(cs, y, z)], b[density_index(0, y, z)]);
+        }
+    }
+}
+
+#[test]
+fn test_density_continuity_across_sections() {
+    let generator = DensityGenerator::new(42);
+    let below = generator.generate_padded_density(XYZCoords { x: 0, y: 0, z: 0 }).chunk_only();
+    let above = generator.generate_padded_density(XYZCoords { x: 0, y: 1, z: 0 }).chunk_only();
+    let ss = SECTION_SIZE as usize;
+
+    for z in 0..DENSITY_DIM {
+        for x in 0..DENSITY_DIM {
+            assert_eq!(below[density_index(x, ss, z)], above[density_index(x, 0, z)]);
+        }
+    }
+}
+
+#[test]
+fn test_surface_sections_contain_surface() {
+    let generator = DensityGenerator::new(42);
+    let heightmap = HeightmapGenerator::new(42);
+    let column = XZCoords { x: 2, z: -3 };
+    let sections = generator.surface_sections(column);
+
+    for z in 0..=CHUNK_SIZE {
+        for x in 0..=CHUNK_SIZE {
+            let world_pos = column.to_world_pos(x, z);
+            let section = section_of(heightmap.sample_height(world_pos.x as f64, world_pos.z as f64));
+            assert!(sections.contains(&section), "Section {} should be generated for the surface", section);
+        }
+    }
+    // everything above the top section is air
+    let top = (*sections.end() + 1) * SECTION_SIZE;
+    let corner = column.to_world_pos(0, 0);
+    assert!(generator.density_at(corner.x as f64, top as f64, corner.z as f64) < 0.0);
+}
+
+#[test]
+fn test_density_mesh_empty_when_uniform() {
+    let generator = MeshGenerator::new();
+    let coord = XYZCoords { x: 0, y: 0, z: 0 };
+
+    let solid = generator.generate_density_mesh(coord, &PaddedDensity::from_fn(coord, |_, _, _| 1.0));
+    let air = generator.generate_density_mesh(coord, &PaddedDensity::from_fn(coord, |_, _, _| -1.0));
+
+    assert!(solid.vertices.is_empty() && solid.indices.is_empty(), "Solid chunk should have no surface");
+    assert!(air.vertices.is_empty() && air.indices.is_empty(), "Air chunk should have no surface");
+}
+
+#[test]
+fn test_density_mesh_flat_plane() {
+    let generator = MeshGenerator::new();
+    let coord = XYZCoords { x: 0, y: 0, z: 0 };
+    let padded = PaddedDensity::from_fn(coord, |_, y, _| (10.5 - y) as f32);
+    let mesh = generator.generate_density_mesh(coord, &padded);
+
+    assert!(!mesh.indices.is_empty(), "Plane should produce triangles");
+    assert_eq!(mesh.vertices.len(), mesh.normals.len());
+    for v in mesh.vertices.chunks_exact(3) {
+        assert!((v[1] - 10.5).abs() < 1e-4, "Vertex should lie on the plane, got y={}", v[1]);
+    }
+    for n in mesh.normals.chunks_exact(3) {
+        assert!((n[1] - 1.0).abs() < 1e-4, "Plane normals should point up");
+    }
+    for tri in mesh.indices.chunks_exact(3) {
+        let (a, b, c) = (vertex(&mesh, tri[0]), vertex(&mesh, tri[1]), vertex(&mesh, tri[2]));
+        assert!((b - a).cross(&(c - a)).y > 0.0, "Triangles should wind counter-clockwise seen from above");
+    }
+}
+
+#[test]
+fn test_density_mesh_sphere_is_closed() {
+    let generator = MeshGenerator::new();
+    let coord = XYZCoords { x: 0, y: 0, z: 0 };
+    let center = Vector3::new(16.0f32, 16.0, 16.0);
+    let padded = PaddedDensity::from_fn(coord, sphere(16.0, 16.0, 16.0, 7.3));
+    let mesh = generator.generate_density_mesh(coord, &padded);
+
+    assert!(!mesh.indices.is_empty(), "Sphere should produce triangles");
+    for (edge, count) in edge_use_counts(std::slice::from_ref(&mesh)) {
+        assert_eq!(count, 2, "Edge {:?} should be shared by exactly two triangles", edge);
+    }
+    for tri in mesh.indices.chunks_exact(3) {
+        let (a, b, c) = (vertex(&mesh, tri[0]), vertex(&mesh, tri[1]), vertex(&mesh, tri[2]));
+        let centroid = (a + b + c) / 3.0;
+        assert!((b - a).cross(&(c - a)).dot(&(centroid - center)) > 0.0, "Triangles should face outward");
+    }
+}
+
+#[test]
+fn test_density_mesh_seamless_across_chunks() {
+    let generator = MeshGenerator::new();
+    let field = sphere(CHUNK_SIZE as f64, 16.0, 16.0, 9.6);
+    let left = XYZCoords { x: 0, y: 0, z: 0 };
+    let right = XYZCoords { x: 1, y: 0, z: 0 };
+    let mesh_left = generator.generate_density_mesh(left, &PaddedDensity::from_fn(left, &field));
+    let mesh_right = generator.generate_density_mesh(right, &PaddedDensity::from_fn(right, &field));
+
+    assert!(!mesh_left.indices.is_empty() && !mesh_right.indices.is_empty());
+    for (edge, count) in edge_use_counts(&[mesh_left, mesh_right]) {
+        assert_eq!(count, 2, "Edge {:?} should be shared by exactly two triangles across the seam", edge);
+    }
+}
+
+#[test]
+fn test_density_mesh_seamless_across_sections() {
+    let generator = MeshGenerator::new();
+    let field = sphere(16.0, SECTION_SIZE as f64, 16.0, 9.6);
+    let below = XYZCoords { x: 0, y: 0, z: 0 };
+    let above = XYZCoords { x: 0, y: 1, z: 0 };
+    let mesh_below = generator.generate_density_mesh(below, &PaddedDensity::from_fn(below, &field));
+    let mesh_above = generator.generate_density_mesh(above, &PaddedDensity::from_fn(above, &field));
+
+    assert!(!mesh_below.indices.is_empty() && !mesh_above.indices.is_empty());
+    for (edge, count) in edge_use_counts(&[mesh_below, mesh_above]) {
+        assert_eq!(count, 2, "Edge {:?} should be shared by exactly two triangles across the seam", edge);
+    }
+}
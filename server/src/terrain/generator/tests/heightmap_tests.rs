@@ -1,6 +1,6 @@
 use crate::terrain::{
     coords::{XZCoords, CHUNK_SIZE},
-    generator::HeightmapGenerator,
+    generator::{HeightmapGenerator, HeightmapSettings},
 };
 use approx::assert_relative_eq;
 use test_case::test_case;
@@ -111,4 +111,33 @@ fn test_seed_determinism() {
     let heights2 = generator2.generate_chunk(coord);
     
     assert_eq!(heights1, heights2, "Same seed should produce identical heights");
-} 
\ No newline at end of file
+}
+
+#[test]
+fn test_different_seeds_differ() {
+    let coord = XZCoords { x: 0, z: 0 };
+    let heights1 = HeightmapGenerator::new(1).generate_chunk(coord);
+    let heights2 = HeightmapGenerator::new(2).generate_chunk(coord);
+
+    assert!(heights1 != heights2, "Different seeds should produce different heights");
+}
+
+#[test]
+fn test_settings_height_range() {
+    let settings = HeightmapSettings { height_range: 4.0, ..HeightmapSettings::default() };
+    let generator = HeightmapGenerator::with_settings(42, settings);
+    let heights = generator.generate_chunk(XZCoords { x: 3, z: -2 });
+
+    for height in heights {
+        assert!((-4.0..=4.0).contains(&height), "Height {} outside configured range", height);
+    }
+}
+
+#[test]
+fn test_default_settings_match_new() {
+    let coord = XZCoords { x: 1, z: 1 };
+    let heights1 = HeightmapGenerator::new(42).generate_chunk(coord);
+    let heights2 = HeightmapGenerator::with_settings(42, HeightmapSettings::default()).generate_chunk(coord);
+
+    assert_eq!(heights1, heights2, "new() should use the default settings");
+}
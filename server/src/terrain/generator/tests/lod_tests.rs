@@ -0,0 +1,102 @@
+use std::collections::BTreeMap;
+
+use crate::terrain::{
+    coords::{XZCoords, CHUNK_SIZE},
+    generator::{lod_stride, HeightmapGenerator, MeshGenerator, LOD_LEVELS},
+};
+use crate::entity::Mesh;
+
+// Highest and lowest vertex at each z along the world line x = `x`: the surface and its skirt
+fn edge_profile(mesh: &Mesh, x: f32) -> BTreeMap<i32, (f32, f32)> {
+    let mut profile = BTreeMap::new();
+    for v in mesh.vertices.chunks_exact(3).filter(|v| v[0] == x) {
+        let entry = profile.entry(v[2] as i32).or_insert((f32::MIN, f32::MAX));
+        entry.0 = entry.0.max(v[1]);
+        entry.1 = entry.1.min(v[1]);
+    }
+    profile
+}
+
+// Linear interpolation of a sparse profile at integer z
+fn profile_at(profile: &BTreeMap<i32, (f32, f32)>, z: i32) -> (f32, f32) {
+    let (&z0, &(top0, bottom0)) = profile.range(..=z).next_back().unwrap();
+    let Some((&z1, &(top1, bottom1))) = profile.range(z + 1..).next() else {
+        return (top0, bottom0);
+    };
+    let t = (z - z0) as f32 / (z1 - z0) as f32;
+    (top0 + (top1 - top0) * t, bottom0 + (bottom1 - bottom0) * t)
+}
+
+#[test]
+fn test_lod_mesh_sizes() {
+    let generator = HeightmapGenerator::new(42);
+    let coord = XZCoords { x: 0, z: 0 };
+    let padded = generator.generate_padded_heightmap(coord);
+
+    for lod in 0..LOD_LEVELS {
+        let mesh = MeshGenerator::new().generate_lod_mesh(coord, &padded, lod);
+        let cells = CHUNK_SIZE as usize / lod_stride(lod);
+        assert_eq!(mesh.vertices.len() / 3, (cells + 1) * (cells + 1) + 4 * (cells + 1), "LOD {}", lod);
+        assert_eq!(mesh.indices.len() / 3, cells * cells * 2 + 4 * cells * 2, "LOD {}", lod);
+        assert_eq!(mesh.normals.len(), mesh.vertices.len());
+    }
+}
+
+#[test]
+fn test_lod_vertices_sample_heightmap() {
+    let generator = HeightmapGenerator::new(42);
+    let coord = XZCoords { x: -2, z: 3 };
+    let padded = generator.generate_padded_heightmap(coord);
+    let mesh = MeshGenerator::new().generate_lod_mesh(coord, &padded, 2);
+
+    // the second vertex of the first row is 4 samples in
+    assert_eq!(mesh.vertices[3], (coord.x * CHUNK_SIZE + 4) as f32);
+    assert_eq!(mesh.vertices[4], padded.get(4, 0));
+    assert_eq!(mesh.vertices[5], (coord.z * CHUNK_SIZE) as f32);
+}
+
+#[test]
+fn test_lod_skirts_cover_cracks() {
+    let generator = HeightmapGenerator::new(42);
+    let (a, b) = (XZCoords { x: 0, z: 0 }, XZCoords { x: 1, z: 0 });
+    let (padded_a, padded_b) = (generator.generate_padded_heightmap(a), generator.generate_padded_heightmap(b));
+    let border = CHUNK_SIZE as f32;
+
+    for lod_a in 0..LOD_LEVELS {
+        for lod_b in 0..LOD_LEVELS {
+            let mesh_a = MeshGenerator::new().generate_lod_mesh(a, &padded_a, lod_a);
+            let mesh_b = MeshGenerator::new().generate_lod_mesh(b, &padded_b, lod_b);
+            let (profile_a, profile_b) = (edge_profile(&mesh_a, border), edge_profile(&mesh_b, border));
+
+            for z in 0..=CHUNK_SIZE {
+                let (top_a, bottom_a) = profile_at(&profile_a, z);
+                let (top_b, bottom_b) = profile_at(&profile_b, z);
+                // whichever side is higher, its skirt reaches down past the other surface
+                assert!(bottom_a <= top_b + 1e-4 && bottom_b <= top_a + 1e-4,
+                    "Crack at z={} between LOD {} and {}", z, lod_a, lod_b);
+            }
+        }
+    }
+}
+
+#[test]
+fn test_lod_normals_match_full_mesh() {
+    let generator = HeightmapGenerator::new(42);
+    let coord = XZCoords { x: 2, z: -1 };
+    let padded = generator.generate_padded_heightmap(coord);
+    let full = MeshGenerator::new().generate_dual_contour_mesh(coord, &padded);
+    let grid = CHUNK_SIZE as usize + 1;
+
+    for lod in 0..LOD_LEVELS {
+        let stride = lod_stride(lod);
+        let cells = CHUNK_SIZE as usize / stride;
+        let mesh = MeshGenerator::new().generate_lod_mesh(coord, &padded, lod);
+        for z in 0..=cells {
+            for x in 0..=cells {
+                let l = (z * (cells + 1) + x) * 3;
+                let f = (z * stride * grid + x * stride) * 3;
+                assert_eq!(mesh.normals[l..l + 3], full.normals[f..f + 3], "LOD {} normal at ({}, {})", lod, x, z);
+            }
+        }
+    }
+}
@@ -0,0 +1,115 @@
+use crate::terrain::{
+    coords::XZCoords,
+    generator::{default_material_rules, Biome, BiomeGenerator, HeightmapGenerator, MaterialGenerator, MeshGenerator, PaddedHeightmap},
+    material::{MaterialRule, MATERIAL_GRASS, MATERIAL_ROCK, MATERIAL_SAND, MATERIAL_SNOW},
+};
+use nalgebra::Vector3;
+use test_case::test_case;
+
+#[test_case(-5.0, MATERIAL_SAND ; "sand near sea level")]
+#[test_case(10.0, MATERIAL_GRASS ; "grass in the lowlands")]
+#[test_case(30.0, MATERIAL_SNOW ; "snow on peaks")]
+fn test_material_height_bands(height: f32, expected: u32) {
+    let generator = MaterialGenerator::new(42);
+    for x in 0..16 {
+        let position = Vector3::new(x as f32 * 7.0, height, 3.0);
+        assert_eq!(generator.material_at(&position, &Vector3::y(), None), expected);
+    }
+}
+
+#[test]
+fn test_steep_slopes_are_rock() {
+    let generator = MaterialGenerator::new(42);
+    let steep = Vector3::new(1.0, 0.5, 0.0).normalize();
+
+    for height in [-5.0, 10.0, 30.0] {
+        let position = Vector3::new(0.0, height, 0.0);
+        assert_eq!(generator.material_at(&position, &steep, None), MATERIAL_ROCK);
+    }
+}
+
+#[test]
+fn test_one_material_per_triangle() {
+    let coord = XZCoords { x: 0, z: 0 };
+    let padded = HeightmapGenerator::new(42).generate_padded_heightmap(coord);
+    let mesh = MeshGenerator::new().generate_dual_contour_mesh(coord, &padded);
+    let materials = MaterialGenerator::new(42).assign_materials(&mesh);
+
+    assert_eq!(materials.len(), mesh.indices.len() / 3);
+}
+
+#[test]
+fn test_cliff_face_is_rock() {
+    let coord = XZCoords { x: 0, z: 0 };
+    // a wall 20 blocks high between x=15 and x=16
+    let data = (-1..=33).flat_map(|_| (-1..=33).map(|x| if x <= 15 { 10.0 } else { 30.0 })).collect();
+    let padded = PaddedHeightmap::new(data, 32);
+    let mesh = MeshGenerator::new().generate_dual_contour_mesh(coord, &padded);
+    let materials = MaterialGenerator::new(42).assign_materials(&mesh);
+
+    // quads in the x=15 column span the wall
+    let cliff = &materials[15 * 2..15 * 2 + 2];
+    assert!(cliff.iter().all(|m| *m == MATERIAL_ROCK), "Cliff face should be rock, got {:?}", cliff);
+    assert_eq!(materials[0], MATERIAL_GRASS);
+}
+
+fn biomes() -> BiomeGenerator {
+    BiomeGenerator::new(42, 0.002)
+}
+
+fn rule(material: u32, priority: i32) -> MaterialRule {
+    MaterialRule {
+        id: 0,
+        material,
+        min_height: f32::MIN,
+        max_height: f32::MAX,
+        min_slope: 0.0,
+        max_slope: 180.0,
+        biome: None,
+        noise_threshold: -1.0,
+        priority,
+    }
+}
+
+#[test]
+fn test_highest_priority_rule_wins() {
+    let flat = Vector3::y();
+    let position = Vector3::new(0.0, 10.0, 0.0);
+
+    let generator = MaterialGenerator::with_rules(42, vec![rule(MATERIAL_SAND, 1), rule(MATERIAL_SNOW, 5)], biomes());
+    assert_eq!(generator.material_at(&position, &flat, None), MATERIAL_SNOW);
+
+    // no matching rule falls back to grass
+    let generator = MaterialGenerator::with_rules(42, vec![], biomes());
+    assert_eq!(generator.material_at(&position, &flat, None), MATERIAL_GRASS);
+}
+
+#[test]
+fn test_rule_biome_and_noise_filters() {
+    let flat = Vector3::y();
+    let position = Vector3::new(0.0, 10.0, 0.0);
+
+    let desert_sand = MaterialRule { biome: Some(Biome::Desert.id()), ..rule(MATERIAL_SAND, 10) };
+    let generator = MaterialGenerator::with_rules(42, vec![desert_sand, rule(MATERIAL_GRASS, 0)], biomes());
+    assert_eq!(generator.material_at(&position, &flat, Some(Biome::Desert.id())), MATERIAL_SAND);
+    assert_eq!(generator.material_at(&position, &flat, Some(Biome::Mountains.id())), MATERIAL_GRASS);
+    assert_eq!(generator.material_at(&position, &flat, None), MATERIAL_GRASS);
+
+    // noise never reaches above 1, so this rule can't match
+    let never = MaterialRule { noise_threshold: 2.0, ..rule(MATERIAL_ROCK, 10) };
+    let generator = MaterialGenerator::with_rules(42, vec![never, rule(MATERIAL_GRASS, 0)], biomes());
+    assert_eq!(generator.material_at(&position, &flat, None), MATERIAL_GRASS);
+}
+
+#[test]
+fn test_default_rules_match_default_generator() {
+    let rules = MaterialGenerator::with_rules(42, default_material_rules(), biomes());
+    let defaults = MaterialGenerator::new(42);
+    let steep = Vector3::new(1.0, 0.5, 0.0).normalize();
+
+    for height in [-5.0, 10.0, 30.0] {
+        let position = Vector3::new(3.0, height, 7.0);
+        assert_eq!(rules.material_at(&position, &steep, None), defaults.material_at(&position, &steep, None));
+        assert_eq!(rules.material_at(&position, &Vector3::y(), None), defaults.material_at(&position, &Vector3::y(), None));
+    }
+}
@@ -1,7 +1,6 @@
 use crate::terrain::{
     coords::{XZCoords, CHUNK_SIZE},
     generator::{MeshGenerator, PaddedHeightmap},
-    ChunkVertex,
 };
 use nalgebra::Vector3;
 use log::{info, debug};
@@ -73,7 +72,6 @@ fn test_dual_contour_empty_heightmap() {
     let mesh = generator.generate_dual_contour_mesh(
         XZCoords { x: 0, z: 0 },
         &padded,
-        &Vec::new()
     );
     
     assert_eq!(mesh.vertices.len(), 0, "Empty heightmap should produce no vertices");
@@ -97,7 +95,6 @@ fn test_dual_contour_single_height() {
     let mesh = generator.generate_dual_contour_mesh(
         XZCoords { x: 0, z: 0 },
         &padded,
-        &Vec::new()
     );
     
     // For a 2x2 block, we expect vertices and triangles
@@ -128,8 +125,8 @@ fn test_dual_contour_world_position() {
     let coord1 = XZCoords { x: 0, z: 0 };
     let coord2 = XZCoords { x: 1, z: 0 };
     
-    let mesh1 = generator.generate_dual_contour_mesh(coord1, &padded, &Vec::new());
-    let mesh2 = generator.generate_dual_contour_mesh(coord2, &padded, &Vec::new());
+    let mesh1 = generator.generate_dual_contour_mesh(coord1, &padded);
+    let mesh2 = generator.generate_dual_contour_mesh(coord2, &padded);
     
     // Find corresponding vertices in both meshes
     if !mesh1.vertices.is_empty() && !mesh2.vertices.is_empty() {
@@ -175,7 +172,7 @@ fn test_dual_contour_height_gradient() {
         debug!("{}", row);
     }
     
-    let mesh = generator.generate_dual_contour_mesh(XZCoords { x: 0, z: 0 }, &padded, &Vec::new());
+    let mesh = generator.generate_dual_contour_mesh(XZCoords { x: 0, z: 0 }, &padded);
     
     info!("Generated mesh with {} vertices and {} indices", mesh.vertices.len() / 3, mesh.indices.len());
     
@@ -209,66 +206,26 @@ fn test_dual_contour_height_gradient() {
 #[test]
 fn test_dual_contour_with_neighbors() {
     let generator = MeshGenerator::new();
-    let mut heights = vec![-16.0; CHUNK_SIZE as usize * CHUNK_SIZE as usize];
-    
-    // Create a slope that crosses chunk boundaries
-    for z in 0..CHUNK_SIZE {
-        for x in 0..CHUNK_SIZE {
-            heights[(z * CHUNK_SIZE + x) as usize] = (x as f32) - 16.0;
-        }
-    }
-    
-    let padded = create_padded_heightmap(heights.clone());
-    let coord = XZCoords { x: 0, z: 0 };
-    
-    // Create a neighbor chunk with pre-computed vertices
-    let mut neighbor_vertices = Vec::new();
-    let mut neighbor_normals = Vec::new();
+    // a slope along x that crosses the border between chunks (0, 0) and (1, 0)
+    let slope = |coord: XZCoords| {
+        let dim = CHUNK_SIZE as usize + 3;
+        let data = (0..dim * dim)
+            .map(|i| (coord.x * CHUNK_SIZE + (i % dim) as i32 - 1) as f32 * 0.5 - 16.0)
+            .collect();
+        PaddedHeightmap::new(data, CHUNK_SIZE)
+    };
+    let left = XZCoords { x: 0, z: 0 };
+    let right = XZCoords { x: 1, z: 0 };
+    let left_mesh = generator.generate_dual_contour_mesh(left, &slope(left));
+    let right_mesh = generator.generate_dual_contour_mesh(right, &slope(right));
+
+    // the left chunk's last column is the right chunk's first, from each side's own heightmap
     let grid = CHUNK_SIZE as usize + 1;
-    
-    // Generate a simple grid of vertices for the neighbor
     for z in 0..grid {
-        for x in 0..grid {
-            let world_x = CHUNK_SIZE as f32 + x as f32;
-            let world_z = z as f32;
-            let height = (x as f32) - 16.0;
-            
-            neighbor_vertices.extend_from_slice(&[world_x, height, world_z]);
-            neighbor_normals.extend_from_slice(&[0.0, 1.0, 0.0]); // Simple upward normals
-        }
+        let l = (z * grid + grid - 1) * 3;
+        let r = (z * grid) * 3;
+        assert_eq!(left_mesh.vertices[l..l + 3], right_mesh.vertices[r..r + 3], "border vertex {} differs", z);
+        assert_eq!(left_mesh.normals[l..l + 3], right_mesh.normals[r..r + 3], "border normal {} differs", z);
+        assert!((CHUNK_SIZE as f32..=CHUNK_SIZE as f32 + 1.0).contains(&left_mesh.vertices[l]));
     }
-    
-    let neighbor = ChunkVertex {
-        grid: XZCoords { x: 1, z: 0 },
-        grid_x: 1,
-        grid_z: 0,
-        heightmap: heights.clone(),
-        vertices: neighbor_vertices,
-        normals: neighbor_normals,
-    };
-    
-    let mesh = generator.generate_dual_contour_mesh(coord, &padded, &vec![neighbor]);
-    
-    assert!(!mesh.vertices.is_empty(), "Should generate vertices with neighbor");
-    assert!(!mesh.indices.is_empty(), "Should generate indices with neighbor");
-    assert_eq!(mesh.vertices.len(), mesh.normals.len(), "Should have same number of vertex and normal components");
-    
-    // Verify that vertices near the boundary align with the neighbor
-    if !mesh.vertices.is_empty() {
-        let mut boundary_found = false;
-        for i in (0..mesh.vertices.len()).step_by(3) {
-            let x = mesh.vertices[i];
-            if (x - CHUNK_SIZE as f32).abs() < 0.1 {
-                boundary_found = true;
-                let y = mesh.vertices[i + 1];
-                let _z = mesh.vertices[i + 2];
-                
-                // The height should be close to our slope function
-                let expected_height = 0.0; // At x = CHUNK_SIZE, our slope crosses zero
-                assert!((y - expected_height).abs() < 1.0, 
-                    "Boundary vertex height should match slope, got {} expected {}", y, expected_height);
-            }
-        }
-        assert!(boundary_found, "Should find vertices along chunk boundary");
-    }
-} 
\ No newline at end of file
+}
@@ -1,2 +1,15 @@
 mod heightmap_tests;
-mod mesh_tests; 
\ No newline at end of file
+mod mesh_tests;
+mod density_tests;
+mod sculpt_tests;
+mod material_tests;
+mod biome_tests;
+mod pipeline_tests;
+mod source_tests;
+mod lod_tests;
+mod seam_tests;
+mod packed_tests;
+mod water_tests;
+mod props_tests;
+mod structure_tests;
+mod query_tests;
\ No newline at end of file
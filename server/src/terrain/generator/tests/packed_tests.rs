@@ -0,0 +1,97 @@
+use crate::terrain::{
+    coords::{XYZCoords, XZCoords},
+    generator::{
+        decode_octahedral, encode_octahedral, pack_density_mesh, pack_lod_mesh, pack_materials,
+        unpack_density_mesh, unpack_lod_mesh, HeightmapGenerator, MaterialGenerator, MeshGenerator,
+        PaddedDensity, DENSITY_POSITION_STEP, LOD_LEVELS,
+    },
+};
+
+#[test]
+fn test_octahedral_roundtrip() {
+    let normals = [
+        [0.0, 1.0, 0.0], [0.0, -1.0, 0.0], [1.0, 0.0, 0.0], [0.0, 0.0, -1.0],
+        [0.6, 0.64, -0.48], [-0.36, -0.48, 0.8],
+    ];
+    for n in normals {
+        let d = decode_octahedral(encode_octahedral(n));
+        let dot = n[0] * d[0] + n[1] * d[1] + n[2] * d[2];
+        assert!(dot > 0.999, "{:?} decoded as {:?}", n, d);
+    }
+}
+
+#[test]
+fn test_pack_materials_palette() {
+    let (palette, indices) = pack_materials(&[2, 2, 0, 7, 0]);
+    assert_eq!(palette, vec![2, 0, 7]);
+    assert_eq!(indices, vec![0, 0, 1, 2, 1]);
+}
+
+#[test]
+fn test_packed_lod_roundtrip() {
+    let generator = HeightmapGenerator::new(42);
+    let coord = XZCoords { x: 3, z: -2 };
+    let padded = generator.generate_padded_heightmap(coord);
+    let material_generator = MaterialGenerator::new(42);
+
+    for lod in 0..LOD_LEVELS {
+        let mut mesh = MeshGenerator::new().generate_lod_mesh(coord, &padded, lod);
+        mesh.materials = material_generator.assign_materials(&mesh);
+        let packed = pack_lod_mesh(&mesh, lod);
+        let unpacked = unpack_lod_mesh(coord, &packed, lod);
+
+        assert_eq!(unpacked.indices, mesh.indices, "LOD {}", lod);
+        assert_eq!(unpacked.materials, mesh.materials, "LOD {}", lod);
+        assert_eq!(unpacked.vertices.len(), mesh.vertices.len(), "LOD {}", lod);
+        let tolerance = packed.height_step * 0.5 + 1e-4;
+        for (a, b) in mesh.vertices.iter().zip(&unpacked.vertices) {
+            assert!((a - b).abs() <= tolerance, "LOD {}: {} unpacked as {}", lod, a, b);
+        }
+        for (a, b) in mesh.normals.chunks_exact(3).zip(unpacked.normals.chunks_exact(3)) {
+            assert!(a[0] * b[0] + a[1] * b[1] + a[2] * b[2] > 0.999, "LOD {}: normal {:?} unpacked as {:?}", lod, a, b);
+        }
+    }
+}
+
+#[test]
+fn test_packed_lod_is_compact() {
+    let generator = HeightmapGenerator::new(42);
+    let coord = XZCoords { x: 0, z: 0 };
+    let padded = generator.generate_padded_heightmap(coord);
+    let mut mesh = MeshGenerator::new().generate_lod_mesh(coord, &padded, 0);
+    mesh.materials = MaterialGenerator::new(42).assign_materials(&mesh);
+    let packed = pack_lod_mesh(&mesh, 0);
+
+    let full = (mesh.vertices.len() + mesh.normals.len()) * 4 + (mesh.indices.len() + mesh.materials.len()) * 4;
+    let compact = packed.heights.len() * 2 + packed.normals.len() + packed.skirt_depths.len() * 4
+        + packed.palette.len() * 4 + packed.materials.len();
+    assert!(compact * 8 < full, "packed {} bytes vs {} unpacked", compact, full);
+}
+
+#[test]
+fn test_packed_density_roundtrip() {
+    let coord = XYZCoords { x: -1, y: 0, z: 2 };
+    let padded = PaddedDensity::from_fn(coord, |x, y, z| {
+        (10.0 - ((x + 16.0).powi(2) + (y - 16.0).powi(2) + (z - 80.0).powi(2)).sqrt()) as f32
+    });
+    let mut mesh = MeshGenerator::new().generate_density_mesh(coord, &padded);
+    mesh.materials = MaterialGenerator::new(42).assign_materials(&mesh);
+    assert!(!mesh.indices.is_empty());
+
+    let packed = pack_density_mesh(coord, &mesh);
+    let unpacked = unpack_density_mesh(coord, &packed);
+
+    assert_eq!(unpacked.indices, mesh.indices);
+    assert_eq!(unpacked.materials, mesh.materials);
+    for (a, b) in mesh.vertices.iter().zip(&unpacked.vertices) {
+        assert!((a - b).abs() <= DENSITY_POSITION_STEP * 0.5 + 1e-4, "{} unpacked as {}", a, b);
+    }
+    for (a, b) in mesh.normals.chunks_exact(3).zip(unpacked.normals.chunks_exact(3)) {
+        assert!(a[0] * b[0] + a[1] * b[1] + a[2] * b[2] > 0.999, "normal {:?} unpacked as {:?}", a, b);
+    }
+
+    let full = (mesh.vertices.len() + mesh.normals.len() + mesh.indices.len() + mesh.materials.len()) * 4;
+    let compact = packed.positions.len() * 2 + packed.normals.len() + packed.indices.len() * 2
+        + packed.palette.len() * 4 + packed.materials.len();
+    assert!(compact * 2 < full, "packed {} bytes vs {} unpacked", compact, full);
+}
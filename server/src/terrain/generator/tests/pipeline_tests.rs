@@ -0,0 +1,176 @@
+use crate::terrain::{
+    coords::XZCoords,
+    generator::{
+        default_pipeline, BlendParams, CombineParams, CurveParams, CurvePoint, DomainWarpParams,
+        FractalParams, HeightmapGenerator, HeightmapSettings, NoiseNode, NoisePipeline, ScaleBiasParams,
+        TerraceParams,
+    },
+};
+use approx::assert_relative_eq;
+
+fn fractal(seed_offset: u32) -> FractalParams {
+    FractalParams { frequency: 0.01, octaves: 4, persistence: 0.5, lacunarity: 2.0, seed_offset }
+}
+
+fn sample(nodes: Vec<NoiseNode>) -> f32 {
+    NoisePipeline::new(42, nodes).unwrap().sample(12.5, -40.0)
+}
+
+#[test]
+fn test_default_pipeline_matches_settings() {
+    let settings = HeightmapSettings::default();
+    let coord = XZCoords { x: 2, z: 5 };
+    let heights1 = HeightmapGenerator::with_settings(42, settings).generate_chunk(coord);
+    let heights2 = HeightmapGenerator::with_pipeline(42, settings, default_pipeline(&settings))
+        .unwrap()
+        .generate_chunk(coord);
+
+    assert_eq!(heights1, heights2);
+}
+
+#[test]
+fn test_pipeline_validation() {
+    assert!(NoisePipeline::validate(&[]).is_err(), "Empty pipeline");
+    assert!(NoisePipeline::validate(&[
+        NoiseNode::Min(CombineParams { a: 0, b: 1 }),
+        NoiseNode::Constant(0.0),
+    ]).is_err(), "Forward reference");
+    assert!(NoisePipeline::validate(&[
+        NoiseNode::Fbm(FractalParams { octaves: 0, ..fractal(0) }),
+    ]).is_err(), "No octaves");
+    assert!(NoisePipeline::validate(&[
+        NoiseNode::Constant(0.0),
+        NoiseNode::Curve(CurveParams {
+            source: 0,
+            points: vec![CurvePoint { input: 1.0, output: 0.0 }, CurvePoint { input: -1.0, output: 1.0 }],
+        }),
+    ]).is_err(), "Unsorted curve");
+    assert!(NoisePipeline::validate(&[
+        NoiseNode::Constant(0.0),
+        NoiseNode::Terrace(TerraceParams { source: 0, steps: 0, sharpness: 2.0 }),
+    ]).is_err(), "Terrace without steps");
+}
+
+#[test]
+fn test_pipeline_rejects_non_finite_and_non_positive_values() {
+    let rejects = |nodes: Vec<NoiseNode>, case: &str| assert!(NoisePipeline::validate(&nodes).is_err(), "{}", case);
+    let with_source = |node: NoiseNode| vec![NoiseNode::Constant(0.0), node];
+
+    rejects(vec![NoiseNode::Fbm(FractalParams { frequency: f64::NAN, ..fractal(0) })], "NaN frequency");
+    rejects(vec![NoiseNode::Fbm(FractalParams { frequency: f64::INFINITY, ..fractal(0) })], "Infinite frequency");
+    rejects(vec![NoiseNode::Ridged(FractalParams { persistence: 0.0, ..fractal(0) })], "Zero persistence");
+    rejects(vec![NoiseNode::Ridged(FractalParams { persistence: f64::NAN, ..fractal(0) })], "NaN persistence");
+    rejects(vec![NoiseNode::Billow(FractalParams { lacunarity: -2.0, ..fractal(0) })], "Negative lacunarity");
+    rejects(vec![NoiseNode::Billow(FractalParams { lacunarity: f64::INFINITY, ..fractal(0) })], "Infinite lacunarity");
+    rejects(vec![NoiseNode::Constant(f32::NAN)], "NaN constant");
+    rejects(with_source(NoiseNode::Terrace(TerraceParams { source: 0, steps: 4, sharpness: f32::NAN })), "NaN sharpness");
+    rejects(with_source(NoiseNode::Terrace(TerraceParams { source: 0, steps: 4, sharpness: f32::INFINITY })), "Infinite sharpness");
+    rejects(with_source(NoiseNode::ScaleBias(ScaleBiasParams { source: 0, scale: f32::NAN, bias: 0.0 })), "NaN scale");
+    rejects(with_source(NoiseNode::ScaleBias(ScaleBiasParams { source: 0, scale: 1.0, bias: f32::INFINITY })), "Infinite bias");
+    rejects(
+        with_source(NoiseNode::DomainWarp(DomainWarpParams { source: 0, warp_x: 0, warp_z: 0, strength: f64::NAN })),
+        "NaN warp strength",
+    );
+    rejects(
+        with_source(NoiseNode::Curve(CurveParams {
+            source: 0,
+            points: vec![CurvePoint { input: -1.0, output: 0.0 }, CurvePoint { input: 1.0, output: f32::NAN }],
+        })),
+        "NaN curve output",
+    );
+    rejects(
+        with_source(NoiseNode::Curve(CurveParams {
+            source: 0,
+            points: vec![CurvePoint { input: f32::NEG_INFINITY, output: 0.0 }, CurvePoint { input: 1.0, output: 1.0 }],
+        })),
+        "Infinite curve input",
+    );
+}
+
+#[test]
+fn test_shared_nodes_are_evaluated_once() {
+    // every node reads the one before it twice, which would take 2^63 evaluations recursively
+    let mut nodes = vec![NoiseNode::Fbm(fractal(0))];
+    for i in 0..63 {
+        nodes.push(NoiseNode::Max(CombineParams { a: i, b: i }));
+    }
+
+    assert_eq!(sample(nodes), sample(vec![NoiseNode::Fbm(fractal(0))]));
+}
+
+#[test]
+fn test_combinators() {
+    let constants = || vec![NoiseNode::Constant(-0.5), NoiseNode::Constant(0.5)];
+
+    let mut nodes = constants();
+    nodes.push(NoiseNode::Min(CombineParams { a: 0, b: 1 }));
+    assert_eq!(sample(nodes), -0.5);
+
+    let mut nodes = constants();
+    nodes.push(NoiseNode::Max(CombineParams { a: 0, b: 1 }));
+    assert_eq!(sample(nodes), 0.5);
+
+    // a control of 0 blends halfway
+    let mut nodes = constants();
+    nodes.push(NoiseNode::Constant(0.0));
+    nodes.push(NoiseNode::Blend(BlendParams { a: 0, b: 1, control: 2 }));
+    assert_relative_eq!(sample(nodes), 0.0);
+}
+
+#[test]
+fn test_curve_remap() {
+    let curve = |value| sample(vec![
+        NoiseNode::Constant(value),
+        NoiseNode::Curve(CurveParams {
+            source: 0,
+            points: vec![
+                CurvePoint { input: -1.0, output: -1.0 },
+                CurvePoint { input: 0.0, output: 0.5 },
+                CurvePoint { input: 1.0, output: 1.0 },
+            ],
+        }),
+    ]);
+
+    assert_relative_eq!(curve(-0.5), -0.25);
+    assert_relative_eq!(curve(0.5), 0.75);
+    assert_relative_eq!(curve(-3.0), -1.0);
+    assert_relative_eq!(curve(3.0), 1.0);
+}
+
+#[test]
+fn test_terrace_flattens_levels() {
+    let terrace = |value, sharpness| sample(vec![
+        NoiseNode::Constant(value),
+        NoiseNode::Terrace(TerraceParams { source: 0, steps: 4, sharpness }),
+    ]);
+
+    // sharpness 1 keeps the input
+    assert_relative_eq!(terrace(0.3, 1.0), 0.3, epsilon = 1e-5);
+    // very sharp terraces snap down to the level below
+    assert_relative_eq!(terrace(0.3, 64.0), 0.0, epsilon = 1e-3);
+    assert_relative_eq!(terrace(0.6, 64.0), 0.5, epsilon = 1e-3);
+}
+
+#[test]
+fn test_fractal_sources_stay_in_range() {
+    for node in [NoiseNode::Fbm(fractal(0)), NoiseNode::Ridged(fractal(1)), NoiseNode::Billow(fractal(2))] {
+        let pipeline = NoisePipeline::new(42, vec![node.clone()]).unwrap();
+        for i in 0..200 {
+            let value = pipeline.sample(i as f64 * 13.7, i as f64 * -7.3);
+            assert!((-1.0..=1.0).contains(&value), "{:?} produced {}", node, value);
+        }
+    }
+}
+
+#[test]
+fn test_domain_warp_moves_samples() {
+    let warp = |strength| sample(vec![
+        NoiseNode::Fbm(fractal(0)),
+        NoiseNode::Fbm(fractal(7)),
+        NoiseNode::Fbm(fractal(8)),
+        NoiseNode::DomainWarp(DomainWarpParams { source: 0, warp_x: 1, warp_z: 2, strength }),
+    ]);
+
+    assert_eq!(warp(0.0), sample(vec![NoiseNode::Fbm(fractal(0))]));
+    assert_ne!(warp(40.0), warp(0.0));
+}
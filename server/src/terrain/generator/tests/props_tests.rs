@@ -0,0 +1,78 @@
+use crate::terrain::{
+    coords::{XZCoords, CHUNK_SIZE},
+    generator::{
+        BiomeGenerator, FlatGenerator, HeightmapGenerator, HeightmapSettings, PaddedHeightmap,
+        Prop, PropKind, PropScatter, TerrainGenerator, PROP_SPACING,
+    },
+};
+
+fn scatter(seed: u32, sea_level: f32) -> PropScatter {
+    PropScatter::new(seed, BiomeGenerator::new(seed, HeightmapSettings::default().climate_frequency), sea_level)
+}
+
+// Props of the 5x5 chunks around the origin
+fn region_props(scatter: &PropScatter, padded: impl Fn(XZCoords) -> PaddedHeightmap) -> Vec<Prop> {
+    (-2..=2)
+        .flat_map(|z| (-2..=2).map(move |x| XZCoords { x, z }))
+        .flat_map(|coord| scatter.scatter(coord, &padded(coord)))
+        .collect()
+}
+
+#[test]
+fn test_scatter_is_deterministic() {
+    let generator = HeightmapGenerator::new(42);
+    let coord = XZCoords { x: 3, z: -5 };
+    let padded = generator.generate_padded_heightmap(coord);
+
+    assert_eq!(scatter(42, -100.0).scatter(coord, &padded), scatter(42, -100.0).scatter(coord, &padded));
+    assert_ne!(scatter(42, -100.0).scatter(coord, &padded), scatter(43, -100.0).scatter(coord, &padded));
+    let neighbour = XZCoords { x: 4, z: -5 };
+    let first = scatter(42, -100.0).scatter(coord, &padded);
+    let second = scatter(42, -100.0).scatter(neighbour, &generator.generate_padded_heightmap(neighbour));
+    let offsets = |props: &[Prop], coord: XZCoords| -> Vec<(f32, f32)> {
+        props.iter().map(|p| (p.x - (coord.x * CHUNK_SIZE) as f32, p.z - (coord.z * CHUNK_SIZE) as f32)).collect()
+    };
+    assert_ne!(offsets(&first, coord), offsets(&second, neighbour), "neighbours share a layout");
+}
+
+#[test]
+fn test_props_keep_their_spacing_across_chunks() {
+    let props = region_props(&scatter(7, -100.0), |coord| HeightmapGenerator::new(7).generate_padded_heightmap(coord));
+    assert!(props.len() > 20, "only {} props in 25 chunks", props.len());
+    for (i, a) in props.iter().enumerate() {
+        for b in &props[i + 1..] {
+            let distance = ((a.x - b.x).powi(2) + (a.z - b.z).powi(2)).sqrt();
+            assert!(distance >= PROP_SPACING - 1e-4, "props {:?} and {:?} are {} apart", a, b, distance);
+        }
+    }
+}
+
+#[test]
+fn test_props_stand_on_the_ground() {
+    let generator = FlatGenerator { height: 5.0 };
+    let props = region_props(&scatter(7, 0.0), |coord| generator.generate_padded_heightmap(coord));
+    assert!(!props.is_empty());
+    assert!(props.iter().all(|p| p.y == 5.0));
+    assert!(props.iter().all(|p| (0.75..=1.25).contains(&p.scale)));
+}
+
+#[test]
+fn test_no_props_under_water() {
+    let generator = FlatGenerator { height: -2.0 };
+    let props = region_props(&scatter(7, 0.0), |coord| generator.generate_padded_heightmap(coord));
+    assert!(props.is_empty());
+}
+
+#[test]
+fn test_steep_ground_only_has_rocks() {
+    // a 40 degree slope is too steep for trees and bushes but not for rocks
+    let coord = XZCoords { x: 0, z: 0 };
+    let dim = CHUNK_SIZE as usize + 3;
+    let slope = 40f32.to_radians().tan();
+    let heights = (0..dim * dim).map(|i| 10.0 + (i % dim) as f32 * slope).collect();
+    let padded = PaddedHeightmap::new(heights, CHUNK_SIZE);
+
+    let props: Vec<Prop> = (0..20).flat_map(|seed| scatter(seed, 0.0).scatter(coord, &padded)).collect();
+    assert!(!props.is_empty());
+    assert!(props.iter().all(|p| p.kind == PropKind::Rock));
+}
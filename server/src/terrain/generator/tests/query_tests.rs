@@ -0,0 +1,180 @@
+use std::cell::{Cell, RefCell};
+use std::collections::HashMap;
+
+use nalgebra::Vector3;
+
+use crate::terrain::coords::{Vec3, XZCoords};
+use crate::terrain::generator::{
+    line_of_sight_heights, raycast_heights, surface_normal, FlatGenerator, HeightmapGenerator, TerrainGenerator,
+    HEIGHTMAP_DIM, MAX_RAY_DISTANCE,
+};
+use crate::terrain::query::{StoredHeightmaps, TerrainQuery};
+
+fn flat(height: f32) -> impl Fn(f32, f32) -> f32 {
+    let generator = FlatGenerator { height };
+    move |x, z| generator.sample_height(x as f64, z as f64)
+}
+
+// A ridge along z, 10 blocks high at x = 0 and falling off one block per block
+fn ridge(x: f32, _z: f32) -> f32 {
+    10.0 - x.abs()
+}
+
+#[test]
+fn test_surface_normal() {
+    let up = surface_normal(flat(3.0), 5.0, -7.0);
+    assert!((up - Vector3::y()).norm() < 1e-6);
+
+    // 45 degree slope falling towards +x
+    let normal = surface_normal(ridge, 5.0, 0.0);
+    let expected = Vector3::new(1.0, 1.0, 0.0).normalize();
+    assert!((normal - expected).norm() < 1e-5, "{normal:?}");
+    assert!((normal.norm() - 1.0).abs() < 1e-5);
+}
+
+#[test]
+fn test_raycast_hits_flat_ground() {
+    let hit = raycast_heights(flat(2.0), Vector3::new(0.0, 10.0, 0.0), Vector3::new(1.0, -1.0, 0.0), 100.0)
+        .expect("ray should hit the ground");
+    assert!((hit.point.y - 2.0).abs() < 1e-3, "{hit:?}");
+    assert!((hit.point.x - 8.0).abs() < 1e-3, "{hit:?}");
+    assert!((hit.distance - 8.0 * 2f32.sqrt()).abs() < 1e-3, "{hit:?}");
+    assert!((hit.normal - Vector3::y()).norm() < 1e-6);
+
+    // too short, pointing up, and no direction at all
+    assert!(raycast_heights(flat(2.0), Vector3::new(0.0, 10.0, 0.0), Vector3::new(1.0, -1.0, 0.0), 5.0).is_none());
+    assert!(raycast_heights(flat(2.0), Vector3::new(0.0, 10.0, 0.0), Vector3::y(), 100.0).is_none());
+    assert!(raycast_heights(flat(2.0), Vector3::new(0.0, 10.0, 0.0), Vector3::zeros(), 100.0).is_none());
+}
+
+#[test]
+fn test_raycast_rejects_non_finite_rays_and_caps_distance() {
+    let origin = Vector3::new(0.0, 10.0, 0.0);
+    let down = -Vector3::y();
+    assert!(raycast_heights(flat(2.0), Vector3::new(f32::NAN, 10.0, 0.0), down, 100.0).is_none());
+    assert!(raycast_heights(flat(2.0), origin, Vector3::new(0.0, f32::NEG_INFINITY, 0.0), 100.0).is_none());
+    assert!(raycast_heights(flat(2.0), origin, down, f32::INFINITY).is_none());
+    assert!(raycast_heights(flat(2.0), origin, down, f32::NAN).is_none());
+
+    // ground just past the cap is out of reach however far the ray is asked to go
+    let ground = 10.0 - MAX_RAY_DISTANCE - 1.0;
+    assert!(raycast_heights(flat(ground), origin, down, f32::MAX).is_none());
+    let hit = raycast_heights(flat(ground + 2.0), origin, down, f32::MAX).expect("ground is within the cap");
+    assert!((hit.distance - (MAX_RAY_DISTANCE - 1.0)).abs() < 1e-2, "{hit:?}");
+}
+
+#[test]
+fn test_raycast_starting_underground_hits_at_origin() {
+    let origin = Vector3::new(1.0, 0.0, 1.0);
+    let hit = raycast_heights(flat(2.0), origin, Vector3::x(), 10.0).expect("origin is underground");
+    assert_eq!(hit.distance, 0.0);
+    assert_eq!(hit.point, origin);
+}
+
+#[test]
+fn test_raycast_meets_generated_terrain_on_its_surface() {
+    let generator = HeightmapGenerator::new(42);
+    let height = |x: f32, z: f32| generator.sample_height(x as f64, z as f64);
+    let origin = Vector3::new(0.0, 200.0, 0.0);
+    for direction in [Vector3::new(1.0, -0.5, 0.3), Vector3::new(-0.2, -1.0, 0.7), Vector3::new(0.0, -1.0, 0.0)] {
+        let hit = raycast_heights(height, origin, direction, 1000.0).expect("ray should reach the ground");
+        assert!((hit.point.y - height(hit.point.x, hit.point.z)).abs() < 0.05, "{hit:?}");
+        assert!((origin + direction.normalize() * hit.distance - hit.point).norm() < 1e-3);
+    }
+}
+
+#[test]
+fn test_line_of_sight() {
+    let a = Vector3::new(-20.0, 5.0, 0.0);
+    let b = Vector3::new(20.0, 5.0, 0.0);
+    assert!(line_of_sight_heights(flat(0.0), a, b));
+    assert!(line_of_sight_heights(flat(0.0), b, a));
+    // the ridge's crest stands between them
+    assert!(!line_of_sight_heights(ridge, a, b));
+    assert!(!line_of_sight_heights(ridge, b, a));
+    // looking over the crest
+    assert!(line_of_sight_heights(ridge, Vector3::new(-20.0, 12.0, 0.0), Vector3::new(20.0, 12.0, 0.0)));
+    // a point seen from itself, above and below the ground
+    assert!(line_of_sight_heights(flat(0.0), a, a));
+    assert!(!line_of_sight_heights(flat(10.0), a, a));
+}
+
+
+/// Heightmaps held in memory, counting how often the query asks for one.
+#[derive(Default)]
+struct MemoryHeightmaps {
+    heightmaps: HashMap<XZCoords, Vec<f32>>,
+    loads: RefCell<Vec<XZCoords>>,
+}
+
+impl StoredHeightmaps for MemoryHeightmaps {
+    fn stored_heightmap(&self, grid: XZCoords) -> Option<Vec<f32>> {
+        self.loads.borrow_mut().push(grid);
+        self.heightmaps.get(&grid).cloned()
+    }
+}
+
+// A stored chunk at the origin whose corner heights rise one block per block along x
+fn stored_slope() -> MemoryHeightmaps {
+    let heightmap = (0..HEIGHTMAP_DIM * HEIGHTMAP_DIM).map(|i| (i % HEIGHTMAP_DIM) as f32).collect();
+    MemoryHeightmaps { heightmaps: HashMap::from([(XZCoords { x: 0, z: 0 }, heightmap)]), ..Default::default() }
+}
+
+#[test]
+fn test_query_reads_stored_chunks_without_the_generator() {
+    let stored = stored_slope();
+    let builds = Cell::new(0);
+    let query = TerrainQuery::from_parts(&stored, || {
+        builds.set(builds.get() + 1);
+        Box::new(FlatGenerator { height: -50.0 })
+    });
+
+    assert_eq!(query.height_at(0.0, 0.0), 0.0);
+    assert!((query.height_at(12.5, 30.0) - 12.5).abs() < 1e-5);
+    assert_eq!(query.stored_height_at(31.75, 7.0), Some(31.75));
+    let normal = query.normal_at(16.0, 16.0);
+    assert!((Vector3::from(normal) - Vector3::new(-1.0, 1.0, 0.0).normalize()).norm() < 1e-5, "{normal:?}");
+    assert_eq!(builds.get(), 0, "every lookup landed on the stored chunk");
+}
+
+#[test]
+fn test_query_falls_back_to_the_generator() {
+    let stored = stored_slope();
+    let builds = Cell::new(0);
+    let query = TerrainQuery::from_parts(&stored, || {
+        builds.set(builds.get() + 1);
+        Box::new(FlatGenerator { height: 30.0 })
+    });
+
+    assert_eq!(query.stored_height_at(-0.5, 3.0), None);
+    assert_eq!(builds.get(), 0, "stored lookups never build the generator");
+    assert_eq!(query.height_at(-0.5, 3.0), 30.0);
+    assert_eq!(query.height_at(100.0, -100.0), 30.0);
+    // the ray passes over the stored slope and into the generated wall beyond it
+    let hit = query
+        .raycast(Vec3 { x: 10.0, y: 20.0, z: 10.0 }, Vec3 { x: -1.0, y: 0.0, z: 0.0 }, 100.0)
+        .expect("the ray should meet the generated terrain");
+    assert!(hit.point.x.abs() < 1e-2 && (hit.distance - 10.0).abs() < 1e-2, "{hit:?}");
+    assert_eq!(builds.get(), 1, "the generator is built once");
+}
+
+#[test]
+fn test_query_caches_each_chunk() {
+    let stored = stored_slope();
+    let query = TerrainQuery::from_parts(&stored, || Box::new(FlatGenerator { height: 0.0 }));
+
+    for i in 0..64 {
+        let offset = i as f32 * 0.5;
+        query.height_at(offset, offset);
+        query.height_at(-1.0 - offset, offset);
+    }
+    query.raycast(Vec3 { x: 1.0, y: 40.0, z: 1.0 }, Vec3 { x: 1.0, y: -0.1, z: 0.0 }, 60.0);
+
+    let mut loads = stored.loads.borrow().clone();
+    let count = loads.len();
+    loads.sort_by_key(|grid| (grid.x, grid.z));
+    loads.dedup();
+    assert_eq!(count, loads.len(), "a chunk was loaded twice: {loads:?}");
+    // the misses are remembered too
+    assert!(loads.contains(&XZCoords { x: -1, z: 0 }));
+}
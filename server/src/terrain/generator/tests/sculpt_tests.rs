@@ -0,0 +1,67 @@
+use crate::terrain::{
+    coords::{DensityDelta, Vec3},
+    generator::{merge_deltas, sculpt_density, sculpt_height, SculptMode},
+};
+use approx::assert_relative_eq;
+
+#[test]
+fn test_dig_lowers_surface_to_sphere_bottom() {
+    let center = Vec3::new(0.0, 10.0, 0.0);
+
+    let height = sculpt_height(SculptMode::Dig, 10.0, &center, 4.0, 0.0, 0.0);
+    assert_relative_eq!(height.unwrap(), 6.0);
+
+    // off-centre columns are dug less deep
+    let height = sculpt_height(SculptMode::Dig, 10.0, &center, 5.0, 3.0, 0.0);
+    assert_relative_eq!(height.unwrap(), 6.0);
+}
+
+#[test]
+fn test_fill_raises_surface_to_sphere_top() {
+    let center = Vec3::new(0.0, 10.0, 0.0);
+    let height = sculpt_height(SculptMode::Fill, 10.0, &center, 4.0, 0.0, 0.0);
+
+    assert_relative_eq!(height.unwrap(), 14.0);
+}
+
+#[test]
+fn test_sculpt_height_ignores_unreached_columns() {
+    let center = Vec3::new(0.0, 10.0, 0.0);
+
+    // outside the sphere's footprint
+    assert_eq!(sculpt_height(SculptMode::Dig, 10.0, &center, 4.0, 5.0, 0.0), None);
+    // sphere floats above the surface
+    assert_eq!(sculpt_height(SculptMode::Dig, 0.0, &center, 4.0, 0.0, 0.0), None);
+    // sphere is buried below the surface
+    assert_eq!(sculpt_height(SculptMode::Fill, 20.0, &center, 4.0, 0.0, 0.0), None);
+}
+
+#[test]
+fn test_sculpt_density() {
+    let center = Vec3::new(0.0, 0.0, 0.0);
+
+    let dug = sculpt_density(SculptMode::Dig, 5.0, &center, 3.0, 1.0, 0.0, 0.0);
+    assert!(dug < 0.0, "Samples inside a dig should become air");
+    let filled = sculpt_density(SculptMode::Fill, -5.0, &center, 3.0, 1.0, 0.0, 0.0);
+    assert!(filled > 0.0, "Samples inside a fill should become solid");
+
+    // samples outside the sphere keep their density
+    assert_eq!(sculpt_density(SculptMode::Dig, 5.0, &center, 3.0, 10.0, 0.0, 0.0), 5.0);
+    assert_eq!(sculpt_density(SculptMode::Fill, -5.0, &center, 3.0, 10.0, 0.0, 0.0), -5.0);
+}
+
+#[test]
+fn test_merge_deltas_sums_and_drops_cancelled() {
+    let mut deltas = vec![
+        DensityDelta { index: 4, value: 1.0 },
+        DensityDelta { index: 2, value: -2.0 },
+    ];
+    merge_deltas(&mut deltas, &[
+        DensityDelta { index: 4, value: 0.5 },
+        DensityDelta { index: 2, value: 2.0 },
+        DensityDelta { index: 1, value: 3.0 },
+    ]);
+
+    let merged: Vec<(u32, f32)> = deltas.iter().map(|d| (d.index, d.value)).collect();
+    assert_eq!(merged, vec![(1, 3.0), (4, 1.5)]);
+}
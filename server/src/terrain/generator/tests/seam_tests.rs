@@ -0,0 +1,153 @@
+use std::collections::{BTreeMap, HashMap};
+
+use crate::terrain::{
+    coords::{DensityDelta, XZCoords, CHUNK_SIZE},
+    generator::{apply_height_edits, heightmap_index, merge_deltas, HeightmapGenerator, MeshGenerator, HEIGHTMAP_DIM},
+};
+use crate::entity::Mesh;
+
+// World position of a heightmap corner
+type Corner = (i32, i32);
+
+// Stored edits per chunk, standing in for the `chunk_edit` table
+type Edits = HashMap<XZCoords, Vec<DensityDelta>>;
+
+// The 3x3 block of chunks around the origin, row-major
+fn region() -> Vec<XZCoords> {
+    (-1..=1).flat_map(|z| (-1..=1).map(move |x| XZCoords { x, z })).collect()
+}
+
+// Records a height offset at a world sample in every chunk that shares it, as `dig_sphere` does
+fn record_edit(edits: &mut Edits, gx: i32, gz: i32, value: f32) {
+    for cz in [gz.div_euclid(CHUNK_SIZE), (gz - 1).div_euclid(CHUNK_SIZE)] {
+        for cx in [gx.div_euclid(CHUNK_SIZE), (gx - 1).div_euclid(CHUNK_SIZE)] {
+            let (lx, lz) = (gx - cx * CHUNK_SIZE, gz - cz * CHUNK_SIZE);
+            if (0..=CHUNK_SIZE).contains(&lx) && (0..=CHUNK_SIZE).contains(&lz) {
+                let delta = DensityDelta { index: heightmap_index(lx as usize, lz as usize) as u32, value };
+                merge_deltas(edits.entry(XZCoords { x: cx, z: cz }).or_default(), &[delta]);
+            }
+        }
+    }
+}
+
+// A pit dug across the corner the four chunks around the origin share, reaching into each
+// one's padding
+fn pit_at_origin() -> Edits {
+    let mut edits = Edits::new();
+    for gz in -3..=3 {
+        for gx in -3..=3 {
+            record_edit(&mut edits, gx, gz, -4.0 + 0.5 * (gx * gx + gz * gz) as f32 / 3.0);
+        }
+    }
+    edits
+}
+
+// Generates every chunk in `order` with fresh generators, layering on the stored edits of each
+// chunk and its neighbours the way `load_padded_heightmap` does
+fn generate_with_edits(order: &[XZCoords], edits: &Edits) -> BTreeMap<(i32, i32), Mesh> {
+    let heightmaps = HeightmapGenerator::new(1234);
+    let meshes = MeshGenerator::new();
+    order.iter()
+        .map(|&coord| {
+            let mut padded = heightmaps.generate_padded_heightmap(coord);
+            apply_height_edits(&mut padded, coord, |neighbor| edits.get(&neighbor).cloned());
+            ((coord.x, coord.z), meshes.generate_dual_contour_mesh(coord, &padded))
+        })
+        .collect()
+}
+
+fn generate(order: &[XZCoords]) -> BTreeMap<(i32, i32), Mesh> {
+    generate_with_edits(order, &Edits::new())
+}
+
+// Every ordering of `items`
+fn permutations(items: &[XZCoords]) -> Vec<Vec<XZCoords>> {
+    if items.len() <= 1 {
+        return vec![items.to_vec()];
+    }
+    (0..items.len())
+        .flat_map(|first| {
+            let mut rest = items.to_vec();
+            let head = rest.remove(first);
+            permutations(&rest).into_iter().map(move |mut tail| {
+                tail.insert(0, head);
+                tail
+            })
+        })
+        .collect()
+}
+
+#[test]
+fn test_region_is_independent_of_generation_order() {
+    // every permutation of the four chunks around the edited corner
+    let chunks: Vec<XZCoords> = (-1..=0).flat_map(|z| (-1..=0).map(move |x| XZCoords { x, z })).collect();
+    let edits = pit_at_origin();
+    let reference = generate_with_edits(&chunks, &edits);
+
+    for order in permutations(&chunks) {
+        let meshes = generate_with_edits(&order, &edits);
+        for (grid, mesh) in &reference {
+            let other = &meshes[grid];
+            assert_eq!(mesh.vertices, other.vertices, "chunk {:?} vertices depend on order {:?}", grid, order);
+            assert_eq!(mesh.normals, other.normals, "chunk {:?} normals depend on order {:?}", grid, order);
+            assert_eq!(mesh.indices, other.indices, "chunk {:?} indices depend on order {:?}", grid, order);
+        }
+    }
+}
+
+#[test]
+fn test_edited_corner_matches_across_chunks() {
+    let chunks: Vec<XZCoords> = (-1..=0).flat_map(|z| (-1..=0).map(move |x| XZCoords { x, z })).collect();
+    let meshes = generate_with_edits(&chunks, &pit_at_origin());
+    let vertex = |grid: (i32, i32), lx: usize, lz: usize| {
+        let i = heightmap_index(lx, lz) * 3;
+        meshes[&grid].vertices[i..i + 3].to_vec()
+    };
+
+    // the pit's floor is shared by all four chunks, and each one must have dug it the same
+    let cs = CHUNK_SIZE as usize;
+    assert!(vertex((0, 0), 0, 0)[1] < vertex((0, 0), 8, 8)[1], "edit was not applied");
+    for (grid, lx, lz) in [((-1, -1), cs, cs), ((0, -1), 0, cs), ((-1, 0), cs, 0)] {
+        assert_eq!(vertex((0, 0), 0, 0), vertex(grid, lx, lz), "chunk {:?} disagrees on the edited corner", grid);
+    }
+}
+
+#[test]
+fn test_region_is_watertight() {
+    let cs = CHUNK_SIZE;
+    let meshes = generate(&region());
+
+    // weld by world corner; every chunk sharing a corner must have produced the same vertex
+    let mut welded: HashMap<Corner, [u32; 6]> = HashMap::new();
+    let mut edges: HashMap<(Corner, Corner), u32> = HashMap::new();
+    for (&(gx, gz), mesh) in &meshes {
+        let corner = |i: u32| {
+            let i = i as usize;
+            (gx * cs + (i % HEIGHTMAP_DIM) as i32, gz * cs + (i / HEIGHTMAP_DIM) as i32)
+        };
+        for i in 0..mesh.vertices.len() / 3 {
+            let bits = [
+                mesh.vertices[i * 3].to_bits(), mesh.vertices[i * 3 + 1].to_bits(), mesh.vertices[i * 3 + 2].to_bits(),
+                mesh.normals[i * 3].to_bits(), mesh.normals[i * 3 + 1].to_bits(), mesh.normals[i * 3 + 2].to_bits(),
+            ];
+            let seen = *welded.entry(corner(i as u32)).or_insert(bits);
+            assert_eq!(seen, bits, "chunks disagree on the vertex at {:?}", corner(i as u32));
+        }
+        for tri in mesh.indices.chunks_exact(3) {
+            for (a, b) in [(tri[0], tri[1]), (tri[1], tri[2]), (tri[2], tri[0])] {
+                let (a, b) = (corner(a), corner(b));
+                *edges.entry((a.min(b), a.max(b))).or_default() += 1;
+            }
+        }
+    }
+
+    // only the outside of the region may have open edges
+    let rim = |(x, z): Corner| x == -cs || x == 2 * cs || z == -cs || z == 2 * cs;
+    for ((a, b), count) in edges {
+        match count {
+            2 => {}
+            1 => assert!(rim(a) && rim(b), "open edge {:?}-{:?} inside the region", a, b),
+            _ => panic!("edge {:?}-{:?} shared by {} triangles", a, b, count),
+        }
+    }
+}
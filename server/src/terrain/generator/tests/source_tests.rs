@@ -0,0 +1,51 @@
+use crate::terrain::{
+    coords::{section_of, XYZCoords, XZCoords, CHUNK_SIZE},
+    generator::{DensityGenerator, FlatGenerator, MeshGenerator, TerrainGenerator},
+};
+use approx::assert_relative_eq;
+
+#[test]
+fn test_flat_generator_heights() {
+    let generator = FlatGenerator { height: 7.5 };
+    let padded = generator.generate_padded_heightmap(XZCoords { x: -3, z: 4 });
+
+    for z in -1..=CHUNK_SIZE as isize + 1 {
+        for x in -1..=CHUNK_SIZE as isize + 1 {
+            assert_eq!(padded.get(x, z), 7.5);
+        }
+    }
+}
+
+#[test]
+fn test_flat_generator_density() {
+    let generator = FlatGenerator { height: 7.5 };
+
+    assert!(generator.density_at(10.0, 7.0, -3.0) > 0.0, "Solid below the plane");
+    assert!(generator.density_at(10.0, 8.0, -3.0) < 0.0, "Air above the plane");
+    assert_eq!(generator.surface_sections(XZCoords { x: 0, z: 0 }), section_of(5.5)..=section_of(9.5));
+}
+
+#[test]
+fn test_flat_generator_mesh_is_planar() {
+    let generator = FlatGenerator { height: 7.5 };
+    let coord = XYZCoords { x: 1, y: 0, z: -1 };
+    let mesh = MeshGenerator::new().generate_density_mesh(coord, &generator.generate_padded_density(coord));
+
+    assert!(!mesh.indices.is_empty(), "Mesh should have triangles");
+    for vertex in mesh.vertices.chunks_exact(3) {
+        assert_relative_eq!(vertex[1], 7.5, epsilon = 1e-4);
+    }
+}
+
+#[test]
+fn test_procedural_generator_through_trait() {
+    let generator = DensityGenerator::new(42);
+    let source: &dyn TerrainGenerator = &generator;
+    let coord = XZCoords { x: 2, z: -1 };
+
+    assert_eq!(
+        source.generate_padded_heightmap(coord).chunk_only(),
+        generator.heightmap().generate_padded_heightmap(coord).chunk_only(),
+    );
+    assert_eq!(source.density_at(12.0, 3.0, -20.0), generator.density_at(12.0, 3.0, -20.0));
+}
@@ -0,0 +1,88 @@
+use crate::terrain::{
+    coords::{XZCoords, CHUNK_SIZE},
+    generator::{HeightmapGenerator, Prefab, StructurePlacement, STRUCTURE_BLEND, STRUCTURE_CELL},
+};
+
+// Structures in the 8x8 coarse cells around the origin
+fn region_structures(generator: &HeightmapGenerator) -> Vec<StructurePlacement> {
+    (-4..4)
+        .flat_map(|z| (-4..4).map(move |x| XZCoords { x, z }))
+        .filter_map(|cell| generator.structure_in_cell(cell))
+        .collect()
+}
+
+#[test]
+fn test_placement_is_deterministic() {
+    let structures = region_structures(&HeightmapGenerator::new(42));
+    assert!(!structures.is_empty(), "no structures in 64 cells");
+    assert_eq!(structures, region_structures(&HeightmapGenerator::new(42)));
+    assert_ne!(structures, region_structures(&HeightmapGenerator::new(43)));
+
+    for structure in &structures {
+        assert!(Prefab::from_id(structure.prefab.id()) == Some(structure.prefab));
+        let (half_width, half_depth) = structure.prefab.half_extents();
+        assert_eq!((structure.half_width, structure.half_depth), (half_width, half_depth));
+    }
+}
+
+#[test]
+fn test_footprint_is_flat() {
+    let generator = HeightmapGenerator::new(7);
+    for structure in region_structures(&generator) {
+        for (fx, fz) in [(0.0, 0.0), (-1.0, -1.0), (1.0, -1.0), (-1.0, 1.0), (1.0, 1.0), (0.5, -0.25)] {
+            let x = structure.x + fx * structure.half_width;
+            let z = structure.z + fz * structure.half_depth;
+            let height = generator.sample_height(x as f64, z as f64);
+            assert!((height - structure.y).abs() < 1e-4, "{:?} at ({x}, {z}) is {height}", structure.prefab);
+        }
+    }
+}
+
+#[test]
+fn test_blend_is_continuous_and_ends_at_natural_terrain() {
+    let generator = HeightmapGenerator::new(7);
+    for structure in region_structures(&generator) {
+        // walk out from the centre past the blend, checking for steps between neighbouring columns
+        let end = structure.half_width + STRUCTURE_BLEND + 4.0;
+        let mut previous = generator.sample_height(structure.x as f64, structure.z as f64);
+        let mut x = 0.25;
+        while x <= end {
+            let height = generator.sample_height((structure.x + x) as f64, structure.z as f64);
+            assert!((height - previous).abs() < 2.0, "step of {} at {x} past the centre", height - previous);
+            previous = height;
+            x += 0.25;
+        }
+        assert_eq!(structure.weight_at(structure.x + end, structure.z), 0.0);
+    }
+}
+
+#[test]
+fn test_every_chunk_under_a_structure_agrees() {
+    let generator = HeightmapGenerator::new(11);
+    let structure = region_structures(&generator)
+        .into_iter()
+        .find(|structure| structure.prefab == Prefab::Ruins)
+        .expect("no ruins in 64 cells");
+
+    let reach = structure.half_width.max(structure.half_depth) + STRUCTURE_BLEND;
+    let chunk_of = |v: f32| (v / CHUNK_SIZE as f32).floor() as i32;
+    let mut touching = 0;
+    for z in chunk_of(structure.z - reach) - 1..=chunk_of(structure.z + reach) + 1 {
+        for x in chunk_of(structure.x - reach) - 1..=chunk_of(structure.x + reach) + 1 {
+            let coord = XZCoords { x, z };
+            let found = generator.structures_touching(coord);
+            if structure.reaches_chunk(coord) {
+                assert_eq!(found, vec![structure], "chunk {x},{z}");
+                touching += 1;
+            } else {
+                assert!(found.iter().all(|other| other.cell != structure.cell), "chunk {x},{z}");
+            }
+        }
+    }
+    assert!(touching >= 4, "ruins only touch {touching} chunks");
+
+    // the structure's own cell is the only one it can be found from
+    let cell_chunks = STRUCTURE_CELL / CHUNK_SIZE;
+    assert_eq!(chunk_of(structure.x).div_euclid(cell_chunks), structure.cell.x);
+    assert_eq!(chunk_of(structure.z).div_euclid(cell_chunks), structure.cell.z);
+}
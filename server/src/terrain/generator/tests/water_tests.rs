@@ -0,0 +1,58 @@
+use crate::terrain::{
+    coords::{XZCoords, CHUNK_SIZE},
+    generator::{
+        is_underwater, is_wet_cell, water_mask, FlatGenerator,
+        HeightmapGenerator, TerrainGenerator, WATER_MASK_BYTES,
+    },
+};
+
+#[test]
+fn test_flat_terrain_floods_below_sea_level() {
+    let coord = XZCoords { x: 2, z: -1 };
+    let padded = FlatGenerator { height: -3.0 }.generate_padded_heightmap(coord);
+    let mask = water_mask(&padded, 0.0);
+    assert_eq!(mask, vec![0xff; WATER_MASK_BYTES]);
+}
+
+#[test]
+fn test_dry_chunk_has_no_water() {
+    let coord = XZCoords { x: 0, z: 0 };
+    let padded = FlatGenerator { height: 1.0 }.generate_padded_heightmap(coord);
+    let mask = water_mask(&padded, 0.0);
+    assert!(mask.is_empty());
+}
+
+#[test]
+fn test_water_covers_cells_below_sea_level() {
+    let generator = HeightmapGenerator::new(42);
+    let sea_level = 0.0;
+    // find a coastline: a chunk with both wet and dry cells
+    let (coord, padded, mask) = (-8..8)
+        .flat_map(|z| (-8..8).map(move |x| XZCoords { x, z }))
+        .map(|coord| {
+            let padded = generator.generate_padded_heightmap(coord);
+            let mask = water_mask(&padded, sea_level);
+            (coord, padded, mask)
+        })
+        .find(|(_, _, mask)| !mask.is_empty() && mask.iter().any(|&byte| byte != 0xff))
+        .expect("no coastline near the origin");
+
+    let cs = CHUNK_SIZE as usize;
+    for z in 0..cs {
+        for x in 0..cs {
+            let (xi, zi) = (x as isize, z as isize);
+            let lowest = [(0, 0), (1, 0), (0, 1), (1, 1)].iter()
+                .map(|&(dx, dz)| padded.get(xi + dx, zi + dz))
+                .fold(f32::MAX, f32::min);
+            assert_eq!(is_wet_cell(&mask, x, z), lowest < sea_level, "cell ({}, {}) of {:?}", x, z, coord);
+        }
+    }
+}
+
+#[test]
+fn test_is_underwater() {
+    assert!(is_underwater(-5.0, -2.0, 0.0));
+    assert!(!is_underwater(-5.0, 1.0, 0.0), "above the surface");
+    assert!(!is_underwater(-5.0, -6.0, 0.0), "inside the ground");
+    assert!(!is_underwater(2.0, 1.0, 0.0), "below the ground on dry land");
+}
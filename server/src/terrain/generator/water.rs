@@ -0,0 +1,42 @@
+use crate::terrain::{
+    coords::CHUNK_SIZE,
+    generator::PaddedHeightmap,
+};
+
+/// Sea level of a new world. Heights are centred on 0, so the lowest ground floods.
+pub const DEFAULT_SEA_LEVEL: f32 = 0.0;
+
+/// Bytes in a chunk's water mask: one bit per cell.
+pub const WATER_MASK_BYTES: usize = (CHUNK_SIZE * CHUNK_SIZE) as usize / 8;
+
+/// Which cells of a chunk hold water, one bit per cell row-major by z. A cell is wet if any
+/// of its corners is below `sea_level`, so the water surface always reaches the shore.
+/// Empty if the whole chunk is dry.
+pub fn water_mask(padded: &PaddedHeightmap, sea_level: f32) -> Vec<u8> {
+    let cs = CHUNK_SIZE as isize;
+    let mut mask = vec![0u8; WATER_MASK_BYTES];
+    let mut wet = false;
+    for z in 0..cs {
+        for x in 0..cs {
+            let lowest = padded.get(x, z).min(padded.get(x + 1, z))
+                .min(padded.get(x, z + 1))
+                .min(padded.get(x + 1, z + 1));
+            if lowest < sea_level {
+                let cell = (z * cs + x) as usize;
+                mask[cell / 8] |= 1 << (cell % 8);
+                wet = true;
+            }
+        }
+    }
+    if wet { mask } else { vec![] }
+}
+
+pub fn is_wet_cell(mask: &[u8], x: usize, z: usize) -> bool {
+    let cell = z * CHUNK_SIZE as usize + x;
+    mask.get(cell / 8).is_some_and(|byte| byte & (1 << (cell % 8)) != 0)
+}
+
+/// Whether a point is in water: below sea level but above the ground beneath it.
+pub fn is_underwater(ground_height: f32, y: f32, sea_level: f32) -> bool {
+    y < sea_level && y > ground_height
+}
@@ -0,0 +1,122 @@
+// src/terrain/import.rs
+
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::rc::Rc;
+
+use spacetimedb::{table, reducer, ReducerContext, Table};
+
+use crate::terrain::coords::{XZCoords, CHUNK_SIZE};
+use crate::terrain::generator::{DensityGenerator, TerrainGenerator};
+use crate::terrain::world::admin_config;
+
+/// Samples per edge of an imported tile: a chunk's corners plus one sample of padding per side.
+pub const IMPORTED_TILE_DIM: usize = CHUNK_SIZE as usize + 3;
+
+/// Heights imported from real-world or hand-made data, one tile per chunk.
+#[table(
+    name = imported_tile,
+    index(name = idx_grid_xz, btree(columns = [grid_x, grid_z])),
+    public
+)]
+#[derive(Clone, Debug)]
+pub struct ImportedTile {
+    /// `grid.key()`, so the chunk can be found by primary key.
+    #[primary_key]
+    pub key: i64,
+    pub grid: XZCoords,
+    pub grid_x: i32,
+    pub grid_z: i32,
+    /// IMPORTED_TILE_DIM^2 heights for local -1..=CHUNK_SIZE+1, row-major by z.
+    pub heights: Vec<f32>,
+}
+
+/// Stores (or replaces) one imported tile. Chunks that were already generated keep their
+/// old terrain.
+#[reducer]
+pub fn upload_imported_tile(ctx: &ReducerContext, grid: XZCoords, heights: Vec<f32>) -> Result<(), String> {
+    admin_config(ctx)?;
+    if heights.len() != IMPORTED_TILE_DIM * IMPORTED_TILE_DIM {
+        return Err(format!("a tile needs {} heights, got {}", IMPORTED_TILE_DIM * IMPORTED_TILE_DIM, heights.len()));
+    }
+    if heights.iter().any(|h| !h.is_finite()) {
+        return Err("tile heights must be finite".into());
+    }
+
+    let tile = ImportedTile { key: grid.key(), grid, grid_x: grid.x, grid_z: grid.z, heights };
+    if ctx.db.imported_tile().key().find(grid.key()).is_some() {
+        ctx.db.imported_tile().key().update(tile);
+    } else {
+        ctx.db.imported_tile().insert(tile);
+    }
+    Ok(())
+}
+
+/// Heights from `imported_tile` rows, falling back to the procedural world outside the import.
+/// Caves and overhangs from the procedural world are carved into imported terrain as well.
+pub struct ImportedGenerator<'a> {
+    ctx: &'a ReducerContext,
+    fallback: DensityGenerator,
+    tiles: RefCell<HashMap<XZCoords, Option<Rc<Vec<f32>>>>>,
+}
+
+impl<'a> ImportedGenerator<'a> {
+    pub fn new(ctx: &'a ReducerContext, fallback: DensityGenerator) -> Self {
+        Self { ctx, fallback, tiles: RefCell::new(HashMap::new()) }
+    }
+
+    fn tile(&self, grid: XZCoords) -> Option<Rc<Vec<f32>>> {
+        self.tiles.borrow_mut()
+            .entry(grid)
+            .or_insert_with(|| self.ctx.db.imported_tile().key().find(grid.key()).map(|tile| Rc::new(tile.heights)))
+            .clone()
+    }
+
+    /// Height at an integer world sample, if it's inside the import. Samples on a tile's
+    /// border or padding are also found when only a neighbouring tile, diagonal ones included,
+    /// was uploaded.
+    fn imported_height(&self, x: i32, z: i32) -> Option<f32> {
+        let (gx, gz) = (x.div_euclid(CHUNK_SIZE), z.div_euclid(CHUNK_SIZE));
+        // the sample's own tile first, then all eight around it, whose padding may reach it
+        let mut candidates = std::iter::once((gx, gz)).chain(
+            (-1..=1).flat_map(|dz| (-1..=1).map(move |dx| (gx + dx, gz + dz))).filter(|&tile| tile != (gx, gz)),
+        );
+        candidates.find_map(|(tx, tz)| {
+            // +1 skips the padding row and column
+            let (u, v) = (x - tx * CHUNK_SIZE + 1, z - tz * CHUNK_SIZE + 1);
+            let range = 0..IMPORTED_TILE_DIM as i32;
+            if !range.contains(&u) || !range.contains(&v) {
+                return None;
+            }
+            let tile = self.tile(XZCoords { x: tx, z: tz })?;
+            Some(tile[v as usize * IMPORTED_TILE_DIM + u as usize])
+        })
+    }
+}
+
+impl TerrainGenerator for ImportedGenerator<'_> {
+    fn sample_height(&self, x: f64, z: f64) -> f32 {
+        // bilinear between the four surrounding samples, each of which may come from a different tile
+        let (x0, z0) = (x.floor(), z.floor());
+        let (tx, tz) = ((x - x0) as f32, (z - z0) as f32);
+        let (x0, z0) = (x0 as i32, z0 as i32);
+        let corner = |cx: i32, cz: i32| {
+            self.imported_height(cx, cz)
+                .unwrap_or_else(|| self.fallback.sample_height(cx as f64, cz as f64))
+        };
+        if tx == 0.0 && tz == 0.0 {
+            return corner(x0, z0);
+        }
+        let top = corner(x0, z0) * (1.0 - tx) + corner(x0 + 1, z0) * tx;
+        let bottom = corner(x0, z0 + 1) * (1.0 - tx) + corner(x0 + 1, z0 + 1) * tx;
+        top * (1.0 - tz) + bottom * tz
+    }
+
+    fn density_at(&self, x: f64, y: f64, z: f64) -> f32 {
+        self.fallback.sample_density(x, y, z, self.sample_height(x, z))
+    }
+
+    fn surface_reach(&self) -> f32 {
+        self.fallback.surface_reach()
+    }
+}
@@ -1,9 +1,17 @@
 // src/material.rs
 
 use spacetimedb::{table, reducer, ReducerContext, Table};
-use crate::terrain::coords::MaterialId;
+use crate::terrain::coords::{BiomeId, MaterialId};
+use crate::terrain::generator::default_material_rules;
+use crate::terrain::world::{admin_config, mark_terrain_stale};
 
-#[table(name = material_definition)]
+/// Built-in terrain materials assigned by the generator.
+pub const MATERIAL_GRASS: MaterialId = 0;
+pub const MATERIAL_SAND: MaterialId = 1;
+pub const MATERIAL_ROCK: MaterialId = 2;
+pub const MATERIAL_SNOW: MaterialId = 3;
+
+#[table(name = material_definition, public)]
 #[derive(Clone, Debug)]
 pub struct MaterialDefinition {
     #[primary_key]
@@ -13,8 +21,117 @@ pub struct MaterialDefinition {
     pub texture: Option<String>,
 }
 
+impl MaterialDefinition {
+    fn builtin(id: MaterialId, name: &str, base_color: [f32; 4]) -> Self {
+        Self { id, name: name.into(), base_color: base_color.to_vec(), texture: None }
+    }
+}
+
+/// Decides which material a terrain triangle gets. The generator evaluates rules from the
+/// highest priority down and uses the first one that matches.
+#[table(name = material_rule, public)]
+#[derive(Clone, Debug)]
+pub struct MaterialRule {
+    #[primary_key]
+    #[auto_inc]
+    pub id: u32,
+    pub material: MaterialId,
+    pub min_height: f32,
+    pub max_height: f32,
+    /// Slope in degrees: 0 is flat ground, 90 a vertical wall and 180 a ceiling.
+    pub min_slope: f32,
+    pub max_slope: f32,
+    /// Only match in this biome, or anywhere if `None`.
+    pub biome: Option<BiomeId>,
+    /// Only match where the material noise (-1..1) is at least this, for patchy placement.
+    pub noise_threshold: f32,
+    pub priority: i32,
+}
+
+impl MaterialRule {
+    fn validate(&self, ctx: &ReducerContext) -> Result<(), String> {
+        if ctx.db.material_definition().id().find(self.material).is_none() {
+            return Err(format!("material {} is not defined", self.material));
+        }
+        if self.min_height.is_nan() || self.max_height.is_nan() || self.min_height > self.max_height {
+            return Err("min_height must not be above max_height".into());
+        }
+        if !(0.0..=180.0).contains(&self.min_slope) || !(0.0..=180.0).contains(&self.max_slope)
+            || self.min_slope > self.max_slope
+        {
+            return Err("slopes must satisfy 0 <= min_slope <= max_slope <= 180".into());
+        }
+        if self.noise_threshold.is_nan() {
+            return Err("noise_threshold must be a number".into());
+        }
+        Ok(())
+    }
+}
+
+/// Seeds a definition for each built-in material, leaving any the admin already changed,
+/// and the default material rules if there are none yet. Called from the module `init` reducer.
+pub fn init_materials(ctx: &ReducerContext) {
+    let defaults = [
+        MaterialDefinition::builtin(MATERIAL_GRASS, "grass", [0.30, 0.55, 0.20, 1.0]),
+        MaterialDefinition::builtin(MATERIAL_SAND, "sand", [0.86, 0.80, 0.56, 1.0]),
+        MaterialDefinition::builtin(MATERIAL_ROCK, "rock", [0.45, 0.43, 0.40, 1.0]),
+        MaterialDefinition::builtin(MATERIAL_SNOW, "snow", [0.95, 0.96, 0.98, 1.0]),
+    ];
+    for definition in defaults {
+        if ctx.db.material_definition().id().find(definition.id).is_none() {
+            ctx.db.material_definition().insert(definition);
+        }
+    }
+
+    if ctx.db.material_rule().iter().next().is_none() {
+        for rule in default_material_rules() {
+            ctx.db.material_rule().insert(rule);
+        }
+    }
+}
+
 #[reducer]
 pub fn on_material_defined(ctx: &ReducerContext, e: MaterialDefinition) -> Result<(), String> {
     ctx.db.material_definition().insert(e);
     Ok(())
-}
\ No newline at end of file
+}
+
+/// Adds a material rule; the `id` is assigned by the table. Existing chunks keep their
+/// materials until `reapply_material_rules` is called.
+#[reducer]
+pub fn add_material_rule(ctx: &ReducerContext, rule: MaterialRule) -> Result<(), String> {
+    admin_config(ctx)?;
+    rule.validate(ctx)?;
+    ctx.db.material_rule().insert(MaterialRule { id: 0, ..rule });
+    Ok(())
+}
+
+/// Replaces the material rule with the same `id`.
+#[reducer]
+pub fn update_material_rule(ctx: &ReducerContext, rule: MaterialRule) -> Result<(), String> {
+    admin_config(ctx)?;
+    rule.validate(ctx)?;
+    if ctx.db.material_rule().id().find(rule.id).is_none() {
+        return Err(format!("material rule {} does not exist", rule.id));
+    }
+    ctx.db.material_rule().id().update(rule);
+    Ok(())
+}
+
+#[reducer]
+pub fn remove_material_rule(ctx: &ReducerContext, id: u32) -> Result<(), String> {
+    admin_config(ctx)?;
+    if !ctx.db.material_rule().id().delete(id) {
+        return Err(format!("material rule {} does not exist", id));
+    }
+    Ok(())
+}
+
+/// Marks every generated chunk and section stale, so the background sweep rebuilds them with
+/// the current material rules a few at a time instead of rewriting every mesh in one
+/// transaction. Requested chunks are rebuilt straight away as usual.
+#[reducer]
+pub fn reapply_material_rules(ctx: &ReducerContext) -> Result<(), String> {
+    mark_terrain_stale(ctx, admin_config(ctx)?);
+    Ok(())
+}
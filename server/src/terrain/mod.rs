@@ -1,7 +1,25 @@
 pub mod coords;
 pub mod chunk;
 pub mod material;
+pub mod density;
 pub mod generator;
+pub mod world;
+pub mod edit;
+pub mod biome;
+pub mod import;
+pub mod sweep;
+pub mod queue;
+pub mod water;
+pub mod prop;
+pub mod structure;
+pub mod query;
 
-pub use chunk::{ChunkVertex, ChunkMesh};
-pub use coords::{XZCoords, CHUNK_SIZE, SECTION_SIZE};
\ No newline at end of file
+pub use chunk::{ChunkVertex, ChunkMesh, ChunkMeshLod, ChunkPacked};
+pub use density::{DensityChunk, DensityMesh};
+pub use world::WorldConfig;
+pub use edit::{ChunkEdit, DensityEdit};
+pub use biome::ChunkBiome;
+pub use water::ChunkWater;
+pub use prop::ChunkProp;
+pub use structure::Structure;
+pub use coords::{XZCoords, XYZCoords, CHUNK_SIZE, SECTION_SIZE};
\ No newline at end of file
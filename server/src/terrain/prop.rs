@@ -0,0 +1,69 @@
+// src/terrain/prop.rs
+
+use spacetimedb::{table, ReducerContext, Table};
+
+use crate::terrain::coords::XZCoords;
+use crate::terrain::generator::{PaddedHeightmap, PropKind, PropScatter, StructurePlacement};
+
+/// Trees, bushes and rocks scattered over generated chunks, one row per prop.
+#[table(
+    name = chunk_prop,
+    index(name = idx_grid_xz, btree(columns = [grid_x, grid_z])),
+    public
+)]
+#[derive(Clone, Debug)]
+pub struct ChunkProp {
+    #[primary_key]
+    #[auto_inc]
+    pub id: u64,
+    pub grid: XZCoords,
+    pub grid_x: i32,
+    pub grid_z: i32,
+    /// `PropKind` as its id.
+    pub kind: u32,
+    pub x: f32,
+    pub y: f32,
+    pub z: f32,
+    pub rotation: f32,
+    pub scale: f32,
+}
+
+impl ChunkProp {
+    pub fn kind(&self) -> Option<PropKind> {
+        PropKind::from_id(self.kind)
+    }
+}
+
+/// Replaces a chunk's props with a fresh scatter over its current heights, leaving structure
+/// footprints clear.
+pub fn store_chunk_props(
+    ctx: &ReducerContext,
+    coord: XZCoords,
+    padded_heightmap: &PaddedHeightmap,
+    scatter: &PropScatter,
+    structures: &[StructurePlacement],
+) {
+    let table = ctx.db.chunk_prop();
+    let stale: Vec<u64> = table.idx_grid_xz().filter((coord.x, coord.z)).map(|prop| prop.id).collect();
+    for id in stale {
+        table.id().delete(id);
+    }
+
+    for prop in scatter.scatter(coord, padded_heightmap) {
+        if structures.iter().any(|structure| structure.contains(prop.x, prop.z)) {
+            continue;
+        }
+        table.insert(ChunkProp {
+            id: 0,
+            grid: coord,
+            grid_x: coord.x,
+            grid_z: coord.z,
+            kind: prop.kind.id(),
+            x: prop.x,
+            y: prop.y,
+            z: prop.z,
+            rotation: prop.rotation,
+            scale: prop.scale,
+        });
+    }
+}
@@ -0,0 +1,129 @@
+// src/terrain/query.rs
+
+use std::cell::{OnceCell, RefCell};
+use std::collections::HashMap;
+
+use spacetimedb::ReducerContext;
+
+use crate::terrain::chunk::chunk_vertex;
+use crate::terrain::coords::{Vec3, XZCoords, CHUNK_SIZE};
+use crate::terrain::generator::{
+    line_of_sight_heights, raycast_heights, surface_normal, RayHit, TerrainGenerator, HEIGHTMAP_DIM,
+};
+use crate::terrain::world::WorldConfig;
+
+/// Ground height lookups for gameplay reducers. Heights come from stored chunks, so player
+/// edits count, and from the generator where no chunk has been stored yet. Only the heightmap
+/// surface is seen; caves and overhangs in density sections are not.
+///
+/// Chunks are cached for the query's lifetime, so a reducer making many lookups (a raycast
+/// samples every `RAY_STEP`) should keep one query around. The generator is only built the
+/// first time a lookup lands on a chunk that isn't stored.
+pub struct TerrainQuery<'a> {
+    stored: &'a dyn StoredHeightmaps,
+    build_generator: Box<dyn Fn() -> Box<dyn TerrainGenerator + 'a> + 'a>,
+    generator: OnceCell<Box<dyn TerrainGenerator + 'a>>,
+    heightmaps: RefCell<HashMap<XZCoords, Option<Vec<f32>>>>,
+}
+
+/// Where a `TerrainQuery` reads stored chunk heightmaps from.
+pub trait StoredHeightmaps {
+    /// The chunk's `HEIGHTMAP_DIM` squared corner heights, if it is stored.
+    fn stored_heightmap(&self, grid: XZCoords) -> Option<Vec<f32>>;
+}
+
+impl StoredHeightmaps for ReducerContext {
+    fn stored_heightmap(&self, grid: XZCoords) -> Option<Vec<f32>> {
+        self.db.chunk_vertex().key().find(grid.key()).map(|chunk| chunk.heightmap)
+    }
+}
+
+impl<'a> TerrainQuery<'a> {
+    /// A query that loads the world config only if it needs the generator.
+    pub fn new(ctx: &'a ReducerContext) -> Self {
+        Self::from_parts(ctx, move || WorldConfig::load(ctx).terrain_generator(ctx))
+    }
+
+    pub fn with_config(ctx: &'a ReducerContext, config: &WorldConfig) -> Self {
+        let config = config.clone();
+        Self::from_parts(ctx, move || config.terrain_generator(ctx))
+    }
+
+    /// A query over any heightmap store; `generator` is called at most once, on the first miss.
+    pub fn from_parts(
+        stored: &'a dyn StoredHeightmaps,
+        generator: impl Fn() -> Box<dyn TerrainGenerator + 'a> + 'a,
+    ) -> Self {
+        Self {
+            stored,
+            build_generator: Box::new(generator),
+            generator: OnceCell::new(),
+            heightmaps: RefCell::new(HashMap::new()),
+        }
+    }
+
+    fn generator(&self) -> &dyn TerrainGenerator {
+        self.generator.get_or_init(|| (self.build_generator)()).as_ref()
+    }
+
+    /// Ground height at a world column, bilinear between the chunk's corner heights.
+    pub fn height_at(&self, x: f32, z: f32) -> f32 {
+        self.stored_height_at(x, z)
+            .unwrap_or_else(|| self.generator().sample_height(x as f64, z as f64))
+    }
+
+    /// Ground height at a world column if its chunk is stored, without ever building the
+    /// generator. For hot paths that can do without a height for ungenerated terrain.
+    pub fn stored_height_at(&self, x: f32, z: f32) -> Option<f32> {
+        let grid = XZCoords { x: (x / CHUNK_SIZE as f32).floor() as i32, z: (z / CHUNK_SIZE as f32).floor() as i32 };
+        let mut heightmaps = self.heightmaps.borrow_mut();
+        let heightmap = heightmaps
+            .entry(grid)
+            .or_insert_with(|| self.stored.stored_heightmap(grid))
+            .as_ref()?;
+
+        let (lx, lz) = (x - (grid.x * CHUNK_SIZE) as f32, z - (grid.z * CHUNK_SIZE) as f32);
+        let (x0, z0) = ((lx.floor() as usize).min(CHUNK_SIZE as usize - 1), (lz.floor() as usize).min(CHUNK_SIZE as usize - 1));
+        let (tx, tz) = (lx - x0 as f32, lz - z0 as f32);
+        let h = |x: usize, z: usize| heightmap[z * HEIGHTMAP_DIM + x];
+        let near = h(x0, z0) + (h(x0 + 1, z0) - h(x0, z0)) * tx;
+        let far = h(x0, z0 + 1) + (h(x0 + 1, z0 + 1) - h(x0, z0 + 1)) * tx;
+        Some(near + (far - near) * tz)
+    }
+
+    /// Upward unit normal of the ground at a world column.
+    pub fn normal_at(&self, x: f32, z: f32) -> Vec3 {
+        surface_normal(|x, z| self.height_at(x, z), x, z).into()
+    }
+
+    /// First point within `max_distance`, at most `MAX_RAY_DISTANCE`, where a ray meets the
+    /// ground. A ray with a non-finite origin, direction or distance hits nothing.
+    pub fn raycast(&self, origin: Vec3, direction: Vec3, max_distance: f32) -> Option<RayHit> {
+        raycast_heights(|x, z| self.height_at(x, z), origin.into(), direction.into(), max_distance)
+    }
+
+    /// Whether the straight line between two points above the ground stays clear of it.
+    pub fn line_of_sight(&self, from: Vec3, to: Vec3) -> bool {
+        line_of_sight_heights(|x, z| self.height_at(x, z), from.into(), to.into())
+    }
+}
+
+/// Ground height at a world column. See `TerrainQuery`.
+pub fn height_at(ctx: &ReducerContext, x: f32, z: f32) -> f32 {
+    TerrainQuery::new(ctx).height_at(x, z)
+}
+
+/// Upward unit normal of the ground at a world column.
+pub fn normal_at(ctx: &ReducerContext, x: f32, z: f32) -> Vec3 {
+    TerrainQuery::new(ctx).normal_at(x, z)
+}
+
+/// First point within `max_distance` where a ray meets the ground.
+pub fn raycast(ctx: &ReducerContext, origin: Vec3, direction: Vec3, max_distance: f32) -> Option<RayHit> {
+    TerrainQuery::new(ctx).raycast(origin, direction, max_distance)
+}
+
+/// Whether the straight line between two points above the ground stays clear of it.
+pub fn line_of_sight(ctx: &ReducerContext, from: Vec3, to: Vec3) -> bool {
+    TerrainQuery::new(ctx).line_of_sight(from, to)
+}
@@ -0,0 +1,182 @@
+// src/terrain/queue.rs
+
+use std::time::Duration;
+
+use spacetimedb::{table, reducer, Identity, ReducerContext, ScheduleAt, Table, Timestamp};
+
+use crate::entity::player::player;
+use crate::terrain::chunk::{generate_chunk, is_chunk_current};
+use crate::terrain::coords::{XZCoords, CHUNK_SIZE};
+use crate::terrain::world::WorldConfig;
+
+/// How often the worker generates queued chunks.
+const WORKER_INTERVAL: Duration = Duration::from_millis(250);
+/// Chunks generated per worker tick, so each transaction stays short.
+const CHUNKS_PER_TICK: usize = 2;
+/// Queued chunks are dropped rather than added past this many.
+const MAX_QUEUED_CHUNKS: u64 = 1024;
+/// Chunks around the origin generated after the module is published.
+const SPAWN_RADIUS: i32 = 4;
+/// How many chunks past a moving view to look ahead, and how far around that point to fill in.
+const LOOKAHEAD: i32 = 4;
+const LOOKAHEAD_RADIUS: i32 = 10;
+/// Farthest, in chunks, a client's reported view may be from its player's chunk. Players send
+/// their position a few times a second, so the view can run a little ahead of the stored row.
+const MAX_VIEW_DRIFT: i32 = 2;
+/// Shortest time between two `on_view_moved` calls from one identity that both queue chunks.
+const VIEW_MOVE_INTERVAL: Duration = Duration::from_secs(1);
+/// Lowest priority a queued chunk can have; larger numbers are generated later.
+const MAX_PRIORITY: u32 = (LOOKAHEAD + LOOKAHEAD_RADIUS) as u32;
+
+/// Chunks waiting to be generated in the background, lowest `priority` first.
+#[table(name = chunk_generation_queue)]
+#[derive(Clone, Debug)]
+pub struct ChunkGenerationQueue {
+    /// `grid.key()`, so the chunk can be found by primary key.
+    #[primary_key]
+    pub key: i64,
+    pub grid: XZCoords,
+    pub grid_x: i32,
+    pub grid_z: i32,
+    #[index(btree)]
+    pub priority: u32,
+}
+
+/// When each identity last had chunks queued by `on_view_moved`, to rate-limit it.
+#[table(name = view_move)]
+#[derive(Clone, Debug)]
+pub struct ViewMove {
+    #[primary_key]
+    pub identity: Identity,
+    pub last_queued: Timestamp,
+}
+
+/// Schedule for `generate_queued_chunks`. One row keeps the worker running.
+#[table(name = chunk_generation_worker, scheduled(generate_queued_chunks))]
+pub struct ChunkGenerationWorker {
+    #[primary_key]
+    #[auto_inc]
+    pub scheduled_id: u64,
+    pub scheduled_at: ScheduleAt,
+}
+
+/// Starts the worker unless it is already scheduled.
+pub fn schedule_generation_worker(ctx: &ReducerContext) {
+    if ctx.db.chunk_generation_worker().count() == 0 {
+        ctx.db.chunk_generation_worker().insert(ChunkGenerationWorker {
+            scheduled_id: 0,
+            scheduled_at: WORKER_INTERVAL.into(),
+        });
+    }
+}
+
+/// Queues a chunk unless it is already generated, keeping the more urgent priority if it is
+/// already queued.
+pub fn enqueue_chunk(ctx: &ReducerContext, grid: XZCoords, priority: u32, config: &WorldConfig) {
+    let priority = priority.min(MAX_PRIORITY);
+    let queue = ctx.db.chunk_generation_queue();
+    if let Some(queued) = queue.key().find(grid.key()) {
+        if priority < queued.priority {
+            queue.key().update(ChunkGenerationQueue { priority, ..queued });
+        }
+        return;
+    }
+    if queue.count() >= MAX_QUEUED_CHUNKS || is_chunk_current(ctx, grid, config) {
+        return;
+    }
+    queue.insert(ChunkGenerationQueue { key: grid.key(), grid, grid_x: grid.x, grid_z: grid.z, priority });
+}
+
+/// Queues every chunk within `radius` of `center`, prioritised by how near they are to `focus`.
+fn enqueue_around(ctx: &ReducerContext, center: XZCoords, radius: i32, focus: XZCoords, config: &WorldConfig) {
+    for z in center.z - radius..=center.z + radius {
+        for x in center.x - radius..=center.x + radius {
+            let distance = (x - focus.x).abs().max((z - focus.z).abs()) as u32;
+            enqueue_chunk(ctx, XZCoords { x, z }, distance, config);
+        }
+    }
+}
+
+/// Queues the area around spawn and starts the worker. Called from the module `init` reducer.
+pub fn init_generation_queue(ctx: &ReducerContext) {
+    let config = WorldConfig::load(ctx);
+    let spawn = XZCoords { x: 0, z: 0 };
+    enqueue_around(ctx, spawn, SPAWN_RADIUS, spawn, &config);
+    schedule_generation_worker(ctx);
+}
+
+/// A client's view moved from chunk `from` to chunk `to`. Queues the chunks it is heading
+/// towards, so they are ready before the client requests them. The view is centred on the
+/// caller's player rather than on `to`, which only has to agree with it to within
+/// `MAX_VIEW_DRIFT` chunks, and each identity queues at most once per `VIEW_MOVE_INTERVAL`.
+#[reducer]
+pub fn on_view_moved(ctx: &ReducerContext, from: XZCoords, to: XZCoords) -> Result<(), String> {
+    let player = ctx.db.player().identity().find(ctx.sender).ok_or("player does not exist")?;
+    if !player.online {
+        return Err("player is offline".into());
+    }
+    let position = player.transform.position;
+    let center = XZCoords {
+        x: (position.x / CHUNK_SIZE as f32).floor() as i32,
+        z: (position.z / CHUNK_SIZE as f32).floor() as i32,
+    };
+    if (to.x - center.x).abs().max((to.z - center.z).abs()) > MAX_VIEW_DRIFT {
+        return Err(format!("view is not within {} chunks of the player", MAX_VIEW_DRIFT));
+    }
+
+    let view_moves = ctx.db.view_move();
+    let last = view_moves.identity().find(ctx.sender);
+    if let Some(last) = &last {
+        let since = ctx.timestamp.duration_since(last.last_queued).unwrap_or_default();
+        if since < VIEW_MOVE_INTERVAL {
+            return Err("view moved too recently; try again shortly".into());
+        }
+    }
+    let view_move = ViewMove { identity: ctx.sender, last_queued: ctx.timestamp };
+    match last {
+        Some(_) => {
+            view_moves.identity().update(view_move);
+        }
+        None => {
+            view_moves.insert(view_move);
+        }
+    }
+
+    let (dx, dz) = ((to.x - from.x).signum(), (to.z - from.z).signum());
+    let ahead = XZCoords { x: center.x + dx * LOOKAHEAD, z: center.z + dz * LOOKAHEAD };
+
+    let config = WorldConfig::load(ctx);
+    enqueue_around(ctx, ahead, LOOKAHEAD_RADIUS, center, &config);
+    schedule_generation_worker(ctx);
+    Ok(())
+}
+
+/// Generates the most urgent queued chunks, up to `CHUNKS_PER_TICK`.
+#[reducer]
+pub fn generate_queued_chunks(ctx: &ReducerContext, _worker: ChunkGenerationWorker) -> Result<(), String> {
+    if ctx.sender != ctx.identity() {
+        return Err("generate_queued_chunks can only be run by the scheduler".into());
+    }
+
+    let queue = ctx.db.chunk_generation_queue();
+    let mut batch = Vec::with_capacity(CHUNKS_PER_TICK);
+    for priority in 0..=MAX_PRIORITY {
+        batch.extend(queue.priority().filter(priority).take(CHUNKS_PER_TICK - batch.len()));
+        if batch.len() == CHUNKS_PER_TICK {
+            break;
+        }
+    }
+    if batch.is_empty() {
+        return Ok(());
+    }
+
+    let config = WorldConfig::load(ctx);
+    for queued in batch {
+        queue.key().delete(queued.key);
+        // clients may have requested it since it was queued
+        if !is_chunk_current(ctx, queued.grid, &config) {
+            generate_chunk(ctx, queued.grid, &config);
+        }
+    }
+    Ok(())
+}
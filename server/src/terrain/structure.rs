@@ -0,0 +1,96 @@
+// src/terrain/structure.rs
+
+use spacetimedb::{table, ReducerContext, Table};
+
+use crate::terrain::coords::XZCoords;
+use crate::terrain::generator::{structure_cell, Prefab, StructurePlacement};
+
+/// Structures placed by the generator, one per occupied coarse cell (see `STRUCTURE_CELL`).
+/// The ground under each footprint is already levelled to `y` in the generated heightmap.
+#[table(
+    name = structure,
+    index(name = idx_cell_xz, btree(columns = [cell_x, cell_z])),
+    public
+)]
+#[derive(Clone, Debug)]
+pub struct Structure {
+    /// `cell.key()`, so the cell can be found by primary key.
+    #[primary_key]
+    pub key: i64,
+    pub cell: XZCoords,
+    pub cell_x: i32,
+    pub cell_z: i32,
+    /// `Prefab` as its id.
+    pub prefab: u32,
+    pub x: f32,
+    pub y: f32,
+    pub z: f32,
+    pub half_width: f32,
+    pub half_depth: f32,
+}
+
+impl Structure {
+    pub fn prefab(&self) -> Option<Prefab> {
+        Prefab::from_id(self.prefab)
+    }
+
+    /// The placement this row was stored from, if its prefab is still known.
+    pub fn placement(&self) -> Option<StructurePlacement> {
+        Some(StructurePlacement {
+            cell: self.cell,
+            prefab: self.prefab()?,
+            x: self.x,
+            y: self.y,
+            z: self.z,
+            half_width: self.half_width,
+            half_depth: self.half_depth,
+        })
+    }
+}
+
+impl From<StructurePlacement> for Structure {
+    fn from(placement: StructurePlacement) -> Self {
+        Self {
+            key: placement.cell.key(),
+            cell: placement.cell,
+            cell_x: placement.cell.x,
+            cell_z: placement.cell.z,
+            prefab: placement.prefab.id(),
+            x: placement.x,
+            y: placement.y,
+            z: placement.z,
+            half_width: placement.half_width,
+            half_depth: placement.half_depth,
+        }
+    }
+}
+
+/// Records the structures a chunk touches. Every chunk under a footprint reports the same
+/// placement, so the row is only written by the first and rewritten if the generator moved it.
+/// A stored structure that reaches the chunk but wasn't placed again is removed.
+pub fn store_structures(ctx: &ReducerContext, coord: XZCoords, structures: &[StructurePlacement]) {
+    let table = ctx.db.structure();
+    // a chunk lies inside a single cell, and only that cell's structure can reach it
+    let origin = coord.to_world_pos(0, 0);
+    let cell = structure_cell(origin.x as f64, origin.z as f64);
+    if let Some(stored) = table.key().find(cell.key()) {
+        let placed = structures.iter().any(|placement| placement.cell == cell);
+        if !placed && stored.placement().is_none_or(|placement| placement.reaches_chunk(coord)) {
+            table.key().delete(cell.key());
+        }
+    }
+
+    for &placement in structures {
+        let row = Structure::from(placement);
+        match table.key().find(placement.cell.key()) {
+            None => {
+                table.insert(row);
+            }
+            Some(existing) if existing.prefab != row.prefab || existing.x != row.x
+                || existing.y != row.y || existing.z != row.z => {
+                table.key().update(row);
+            }
+            Some(_) => {}
+        }
+    }
+}
@@ -0,0 +1,69 @@
+// src/terrain/sweep.rs
+
+use std::time::Duration;
+
+use spacetimedb::{table, reducer, ReducerContext, ScheduleAt, Table};
+
+use crate::terrain::chunk::{chunk_vertex, generate_chunk};
+use crate::terrain::coords::{XYZCoords, XZCoords};
+use crate::terrain::density::{density_chunk, load_padded_density, store_density_chunk};
+use crate::terrain::world::WorldConfig;
+
+/// How often the sweep looks for stale chunks.
+const SWEEP_INTERVAL: Duration = Duration::from_secs(1);
+/// Chunks, and separately density sections, rebuilt per sweep so each transaction stays short.
+const SWEEP_BUDGET: usize = 4;
+
+/// Schedule for `sweep_stale_chunks`. One row keeps the sweep running.
+#[table(name = stale_chunk_sweep, scheduled(sweep_stale_chunks))]
+pub struct StaleChunkSweep {
+    #[primary_key]
+    #[auto_inc]
+    pub scheduled_id: u64,
+    pub scheduled_at: ScheduleAt,
+}
+
+/// Starts the background sweep unless it is already scheduled.
+pub fn schedule_stale_chunk_sweep(ctx: &ReducerContext) {
+    if ctx.db.stale_chunk_sweep().count() == 0 {
+        ctx.db.stale_chunk_sweep().insert(StaleChunkSweep {
+            scheduled_id: 0,
+            scheduled_at: SWEEP_INTERVAL.into(),
+        });
+    }
+}
+
+/// Rebuilds a few chunks generated by an older generator version, so stale terrain is replaced
+/// even where no client requests it again. Player edits are reapplied on top.
+#[reducer]
+pub fn sweep_stale_chunks(ctx: &ReducerContext, _sweep: StaleChunkSweep) -> Result<(), String> {
+    if ctx.sender != ctx.identity() {
+        return Err("sweep_stale_chunks can only be run by the scheduler".into());
+    }
+
+    let config = WorldConfig::load(ctx);
+    let current = config.current_generator_version();
+
+    let chunks: Vec<XZCoords> = ctx.db.chunk_vertex().generator_version().filter(0..current)
+        .take(SWEEP_BUDGET)
+        .map(|chunk| chunk.grid)
+        .collect();
+    for coord in chunks {
+        generate_chunk(ctx, coord, &config);
+    }
+
+    let sections: Vec<XYZCoords> = ctx.db.density_chunk().generator_version().filter(0..current)
+        .take(SWEEP_BUDGET)
+        .map(|section| section.coord)
+        .collect();
+    if !sections.is_empty() {
+        let terrain_generator = config.terrain_generator(ctx);
+        let material_generator = config.material_generator(ctx);
+        for coord in sections {
+            let padded_density = load_padded_density(ctx, coord, terrain_generator.as_ref());
+            store_density_chunk(ctx, coord, &padded_density, &material_generator, current);
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,57 @@
+// src/terrain/water.rs
+
+use spacetimedb::{table, ReducerContext, Table};
+
+use crate::terrain::coords::XZCoords;
+use crate::terrain::generator::{water_mask, PaddedHeightmap};
+use crate::terrain::query::TerrainQuery;
+use crate::terrain::world::WorldConfig;
+
+/// Where a chunk's water surface is. Only chunks with at least one wet cell have a row;
+/// clients draw a quad at `sea_level` over each cell set in `mask` (see `water_mask`).
+#[table(
+    name = chunk_water,
+    index(name = idx_grid_xz, btree(columns = [grid_x, grid_z])),
+    public
+)]
+#[derive(Clone, Debug)]
+pub struct ChunkWater {
+    /// `grid.key()`, so the chunk can be found by primary key.
+    #[primary_key]
+    pub key: i64,
+    pub grid: XZCoords,
+    pub grid_x: i32,
+    pub grid_z: i32,
+    pub sea_level: f32,
+    pub mask: Vec<u8>,
+}
+
+/// Stores a chunk's water from its padded heightmap, removing the row if the chunk is dry.
+pub fn store_chunk_water(ctx: &ReducerContext, coord: XZCoords, padded_heightmap: &PaddedHeightmap, sea_level: f32) {
+    let mask = water_mask(padded_heightmap, sea_level);
+    let table = ctx.db.chunk_water();
+    let existing = table.key().find(coord.key()).is_some();
+    if mask.is_empty() {
+        if existing {
+            table.key().delete(coord.key());
+        }
+        return;
+    }
+
+    let row = ChunkWater { key: coord.key(), grid: coord, grid_x: coord.x, grid_z: coord.z, sea_level, mask };
+    if existing {
+        table.key().update(row);
+    } else {
+        table.insert(row);
+    }
+}
+
+/// Whether a world position is in the sea: below sea level and above the ground there.
+pub fn is_underwater(ctx: &ReducerContext, x: f32, y: f32, z: f32) -> bool {
+    let config = WorldConfig::load(ctx);
+    if y >= config.sea_level {
+        return false;
+    }
+    let ground = TerrainQuery::with_config(ctx, &config).height_at(x, z);
+    crate::terrain::generator::is_underwater(ground, y, config.sea_level)
+}
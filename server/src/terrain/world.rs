@@ -0,0 +1,330 @@
+// src/terrain/world.rs
+
+use spacetimedb::{table, reducer, Identity, ReducerContext, SpacetimeType, Table};
+
+use crate::terrain::generator::{
+    BiomeGenerator, DensityGenerator, DensitySettings, HeightmapGenerator, HeightmapSettings,
+    FlatGenerator, MaterialGenerator, NoiseNode, NoisePipeline, PropScatter, TerrainGenerator, DEFAULT_SEA_LEVEL, GENERATOR_VERSION,
+};
+use crate::terrain::import::ImportedGenerator;
+use crate::terrain::material::material_rule;
+use crate::terrain::sweep::schedule_stale_chunk_sweep;
+
+/// Primary key of the single `world_config` row.
+pub const WORLD_CONFIG_ID: u32 = 0;
+/// Seed used when the module is first published.
+pub const DEFAULT_SEED: u32 = 42;
+const MAX_OCTAVES: u32 = 16;
+
+/// Where a realm's terrain heights come from.
+#[derive(SpacetimeType)]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum TerrainSource {
+    /// Procedural noise from the terrain params and noise pipeline.
+    Procedural,
+    /// A flat plane at the given height.
+    Flat(f32),
+    /// `imported_tile` rows, with procedural terrain outside the import.
+    Imported,
+}
+
+/// Per-realm world generation parameters. There is exactly one row, seeded by `init`.
+#[table(name = world_config, public)]
+#[derive(Clone, Debug)]
+pub struct WorldConfig {
+    #[primary_key]
+    pub id: u32,
+    /// Identity allowed to call the world admin reducers (whoever published the module).
+    pub admin: Identity,
+    pub seed: u32,
+    pub terrain_source: TerrainSource,
+    pub base_frequency: f64,
+    pub octaves: u32,
+    pub persistence: f64,
+    pub lacunarity: f64,
+    pub height_range: f32,
+    pub climate_frequency: f64,
+    /// Custom height function; empty means plain fBm from the parameters above.
+    pub noise_pipeline: Vec<NoiseNode>,
+    pub cave_frequency: f64,
+    pub cave_threshold: f32,
+    pub overhang_frequency: f64,
+    pub overhang_strength: f32,
+    /// Height of the water surface; ground below it is under the sea.
+    pub sea_level: f32,
+    /// Raised by `regenerate_terrain`; chunks generated by an older version are rebuilt.
+    pub generator_version: u32,
+}
+
+impl WorldConfig {
+    pub fn new(admin: Identity, seed: u32) -> Self {
+        let defaults = HeightmapSettings::default();
+        let density_defaults = DensitySettings::default();
+        Self {
+            id: WORLD_CONFIG_ID,
+            admin,
+            seed,
+            terrain_source: TerrainSource::Procedural,
+            base_frequency: defaults.base_frequency,
+            octaves: defaults.octaves as u32,
+            persistence: defaults.persistence,
+            lacunarity: defaults.lacunarity,
+            height_range: defaults.height_range,
+            climate_frequency: defaults.climate_frequency,
+            noise_pipeline: vec![],
+            cave_frequency: density_defaults.cave_frequency,
+            cave_threshold: density_defaults.cave_threshold,
+            overhang_frequency: density_defaults.overhang_frequency,
+            overhang_strength: density_defaults.overhang_strength,
+            sea_level: DEFAULT_SEA_LEVEL,
+            generator_version: GENERATOR_VERSION,
+        }
+    }
+
+    /// The stored config, or the defaults if `init` has not seeded one yet.
+    pub fn load(ctx: &ReducerContext) -> Self {
+        ctx.db.world_config().id().find(WORLD_CONFIG_ID)
+            .unwrap_or_else(|| Self::new(ctx.identity(), DEFAULT_SEED))
+    }
+
+    /// Version a stored chunk must have been generated with to be current: the code's version,
+    /// or a later one if the admin has asked for the world to be regenerated since.
+    pub fn current_generator_version(&self) -> u32 {
+        self.generator_version.max(GENERATOR_VERSION)
+    }
+
+    pub fn heightmap_settings(&self) -> HeightmapSettings {
+        HeightmapSettings {
+            base_frequency: self.base_frequency,
+            octaves: self.octaves as usize,
+            persistence: self.persistence,
+            lacunarity: self.lacunarity,
+            height_range: self.height_range,
+            climate_frequency: self.climate_frequency,
+        }
+    }
+
+    pub fn heightmap_generator(&self) -> HeightmapGenerator {
+        if self.noise_pipeline.is_empty() {
+            return HeightmapGenerator::with_settings(self.seed, self.heightmap_settings());
+        }
+        // the pipeline was validated when it was set
+        HeightmapGenerator::with_pipeline(self.seed, self.heightmap_settings(), self.noise_pipeline.clone())
+            .unwrap_or_else(|_| HeightmapGenerator::with_settings(self.seed, self.heightmap_settings()))
+    }
+
+    pub fn density_settings(&self) -> DensitySettings {
+        DensitySettings {
+            cave_frequency: self.cave_frequency,
+            cave_threshold: self.cave_threshold,
+            overhang_frequency: self.overhang_frequency,
+            overhang_strength: self.overhang_strength,
+        }
+    }
+
+    pub fn density_generator(&self) -> DensityGenerator {
+        DensityGenerator::with_settings(self.heightmap_generator(), self.seed, self.density_settings())
+    }
+
+    /// Generator for the configured terrain source. Chunk and edit reducers go through this
+    /// rather than building a particular generator themselves.
+    pub fn terrain_generator<'a>(&self, ctx: &'a ReducerContext) -> Box<dyn TerrainGenerator + 'a> {
+        match self.terrain_source {
+            TerrainSource::Procedural => Box::new(self.density_generator()),
+            TerrainSource::Flat(height) => Box::new(FlatGenerator { height }),
+            TerrainSource::Imported => Box::new(ImportedGenerator::new(ctx, self.density_generator())),
+        }
+    }
+
+    pub fn biome_generator(&self) -> BiomeGenerator {
+        BiomeGenerator::new(self.seed, self.climate_frequency)
+    }
+
+    /// Tree, bush and rock scatter for the current seed, climate and sea level.
+    pub fn prop_scatter(&self) -> PropScatter {
+        PropScatter::new(self.seed, self.biome_generator(), self.sea_level)
+    }
+
+    /// Material generator evaluating the current `material_rule` rows.
+    pub fn material_generator(&self, ctx: &ReducerContext) -> MaterialGenerator {
+        MaterialGenerator::with_rules(self.seed, ctx.db.material_rule().iter().collect(), self.biome_generator())
+    }
+
+    pub fn validate(&self) -> Result<(), String> {
+        if let TerrainSource::Flat(height) = self.terrain_source {
+            if !height.is_finite() {
+                return Err("flat terrain height must be finite".into());
+            }
+        }
+        if !(self.base_frequency.is_finite() && self.base_frequency > 0.0) {
+            return Err("base_frequency must be finite and positive".into());
+        }
+        if self.octaves == 0 || self.octaves > MAX_OCTAVES {
+            return Err(format!("octaves must be between 1 and {}", MAX_OCTAVES));
+        }
+        if !(self.persistence > 0.0 && self.persistence <= 1.0) {
+            return Err("persistence must be in (0, 1]".into());
+        }
+        if !(self.lacunarity.is_finite() && self.lacunarity >= 1.0) {
+            return Err("lacunarity must be finite and at least 1".into());
+        }
+        if !(self.height_range.is_finite() && self.height_range > 0.0) {
+            return Err("height_range must be finite and positive".into());
+        }
+        if !(self.climate_frequency.is_finite() && self.climate_frequency > 0.0) {
+            return Err("climate_frequency must be finite and positive".into());
+        }
+        if !self.noise_pipeline.is_empty() {
+            NoisePipeline::validate(&self.noise_pipeline)?;
+        }
+        if !(self.cave_frequency.is_finite() && self.cave_frequency > 0.0
+            && self.overhang_frequency.is_finite() && self.overhang_frequency > 0.0) {
+            return Err("cave and overhang frequencies must be finite and positive".into());
+        }
+        if !(-1.0..=1.0).contains(&self.cave_threshold) {
+            return Err("cave_threshold must be between -1 and 1".into());
+        }
+        if !(self.overhang_strength.is_finite() && self.overhang_strength >= 0.0) {
+            return Err("overhang_strength must be finite and not negative".into());
+        }
+        if !self.sea_level.is_finite() {
+            return Err("sea_level must be finite".into());
+        }
+        Ok(())
+    }
+}
+
+/// Seeds the `world_config` row. Called from the module `init` reducer.
+pub fn init_world_config(ctx: &ReducerContext) {
+    if ctx.db.world_config().id().find(WORLD_CONFIG_ID).is_none() {
+        ctx.db.world_config().insert(WorldConfig::new(ctx.sender, DEFAULT_SEED));
+    }
+}
+
+pub(crate) fn admin_config(ctx: &ReducerContext) -> Result<WorldConfig, String> {
+    let config = ctx.db.world_config().id().find(WORLD_CONFIG_ID)
+        .ok_or("world config has not been initialised")?;
+    if config.admin != ctx.sender {
+        return Err("only the world admin can change the world config".into());
+    }
+    Ok(config)
+}
+
+/// Changes the world seed. Chunks that were already generated keep their old terrain until
+/// `regenerate_terrain` is called.
+#[reducer]
+pub fn set_world_seed(ctx: &ReducerContext, seed: u32) -> Result<(), String> {
+    let config = admin_config(ctx)?;
+    ctx.db.world_config().id().update(WorldConfig { seed, ..config });
+    Ok(())
+}
+
+/// Switches where terrain heights come from. Chunks that were already generated keep their
+/// old terrain until `regenerate_terrain` is called.
+#[reducer]
+pub fn set_terrain_source(ctx: &ReducerContext, terrain_source: TerrainSource) -> Result<(), String> {
+    let config = WorldConfig {
+        terrain_source,
+        ..admin_config(ctx)?
+    };
+    config.validate()?;
+    ctx.db.world_config().id().update(config);
+    Ok(())
+}
+
+/// Changes the fractal noise parameters used to build the heightmap.
+#[reducer]
+pub fn set_terrain_params(
+    ctx: &ReducerContext,
+    base_frequency: f64,
+    octaves: u32,
+    persistence: f64,
+    lacunarity: f64,
+    height_range: f32,
+) -> Result<(), String> {
+    let config = WorldConfig {
+        base_frequency,
+        octaves,
+        persistence,
+        lacunarity,
+        height_range,
+        ..admin_config(ctx)?
+    };
+    config.validate()?;
+    ctx.db.world_config().id().update(config);
+    Ok(())
+}
+
+/// Replaces the height function with a custom noise pipeline, or goes back to plain fBm
+/// from the terrain params if `nodes` is empty.
+#[reducer]
+pub fn set_noise_pipeline(ctx: &ReducerContext, nodes: Vec<NoiseNode>) -> Result<(), String> {
+    let config = WorldConfig {
+        noise_pipeline: nodes,
+        ..admin_config(ctx)?
+    };
+    config.validate()?;
+    ctx.db.world_config().id().update(config);
+    Ok(())
+}
+
+/// Changes the temperature/moisture noise that lays out biomes.
+#[reducer]
+pub fn set_biome_params(ctx: &ReducerContext, climate_frequency: f64) -> Result<(), String> {
+    let config = WorldConfig {
+        climate_frequency,
+        ..admin_config(ctx)?
+    };
+    config.validate()?;
+    ctx.db.world_config().id().update(config);
+    Ok(())
+}
+
+/// Changes the 3D noise that carves caves and overhangs into density chunks.
+#[reducer]
+pub fn set_cave_params(
+    ctx: &ReducerContext,
+    cave_frequency: f64,
+    cave_threshold: f32,
+    overhang_frequency: f64,
+    overhang_strength: f32,
+) -> Result<(), String> {
+    let config = WorldConfig {
+        cave_frequency,
+        cave_threshold,
+        overhang_frequency,
+        overhang_strength,
+        ..admin_config(ctx)?
+    };
+    config.validate()?;
+    ctx.db.world_config().id().update(config);
+    Ok(())
+}
+
+/// Moves the sea surface. Chunks that were already generated keep their old water until
+/// `regenerate_terrain` is called.
+#[reducer]
+pub fn set_sea_level(ctx: &ReducerContext, sea_level: f32) -> Result<(), String> {
+    let config = WorldConfig {
+        sea_level,
+        ..admin_config(ctx)?
+    };
+    config.validate()?;
+    ctx.db.world_config().id().update(config);
+    Ok(())
+}
+
+/// Marks every generated chunk stale, so each one is rebuilt from the current config the next
+/// time it is requested or by the background sweep. Player edits are kept and reapplied.
+#[reducer]
+pub fn regenerate_terrain(ctx: &ReducerContext) -> Result<(), String> {
+    mark_terrain_stale(ctx, admin_config(ctx)?);
+    Ok(())
+}
+
+/// Bumps the generator version so every stored chunk counts as stale, and starts the sweep.
+pub(crate) fn mark_terrain_stale(ctx: &ReducerContext, config: WorldConfig) {
+    let generator_version = config.current_generator_version() + 1;
+    ctx.db.world_config().id().update(WorldConfig { generator_version, ..config });
+    schedule_stale_chunk_sweep(ctx);
+}
@@ -0,0 +1,162 @@
+// src/export.rs
+
+use std::collections::HashMap;
+use std::io::{self, Write};
+
+use base64::Engine;
+use realm_backend::entity::Mesh;
+use realm_backend::terrain::coords::{XZCoords, CHUNK_SIZE};
+use realm_backend::terrain::generator::{HeightmapGenerator, MeshGenerator, HEIGHTMAP_DIM};
+use serde_json::json;
+
+use crate::heightmap::Heightmap;
+
+/// An inclusive rectangle of chunks.
+#[derive(Clone, Copy, Debug)]
+pub struct Region {
+    pub min: XZCoords,
+    pub max: XZCoords,
+}
+
+impl Region {
+    pub fn new(a: XZCoords, b: XZCoords) -> Self {
+        Self {
+            min: XZCoords { x: a.x.min(b.x), z: a.z.min(b.z) },
+            max: XZCoords { x: a.x.max(b.x), z: a.z.max(b.z) },
+        }
+    }
+
+    pub fn chunks(&self) -> impl Iterator<Item = XZCoords> + '_ {
+        (self.min.z..=self.max.z).flat_map(move |z| (self.min.x..=self.max.x).map(move |x| XZCoords { x, z }))
+    }
+
+    /// Corner samples along x and z.
+    pub fn samples(&self) -> (usize, usize) {
+        let samples = |min: i32, max: i32| (max - min + 1) as usize * CHUNK_SIZE as usize + 1;
+        (samples(self.min.x, self.max.x), samples(self.min.z, self.max.z))
+    }
+}
+
+/// Meshes every chunk in the region and welds them into one mesh. Chunk meshes have one vertex
+/// per corner sample, so vertices on shared borders are merged by their world sample.
+pub fn region_mesh(generator: &HeightmapGenerator, region: Region) -> Mesh {
+    let mesh_generator = MeshGenerator::new();
+    let mut welded = HashMap::new();
+    let mut mesh = Mesh { id: 0, vertices: vec![], normals: vec![], indices: vec![], materials: vec![] };
+
+    for coord in region.chunks() {
+        let padded = generator.generate_padded_heightmap(coord);
+        let chunk = mesh_generator.generate_dual_contour_mesh(coord, &padded);
+
+        let remap: Vec<u32> = (0..chunk.vertices.len() / 3)
+            .map(|i| {
+                let x = coord.x * CHUNK_SIZE + (i % HEIGHTMAP_DIM) as i32;
+                let z = coord.z * CHUNK_SIZE + (i / HEIGHTMAP_DIM) as i32;
+                *welded.entry((x, z)).or_insert_with(|| {
+                    mesh.vertices.extend_from_slice(&chunk.vertices[i * 3..i * 3 + 3]);
+                    mesh.normals.extend_from_slice(&chunk.normals[i * 3..i * 3 + 3]);
+                    (mesh.vertices.len() / 3 - 1) as u32
+                })
+            })
+            .collect();
+        mesh.indices.extend(chunk.indices.iter().map(|&i| remap[i as usize]));
+    }
+
+    mesh
+}
+
+/// Generated corner heights of the region, one sample per block.
+pub fn region_heights(generator: &HeightmapGenerator, region: Region) -> Heightmap {
+    let (width, depth) = region.samples();
+    let origin = region.min.to_world_pos(0, 0);
+    let mut heights = Vec::with_capacity(width * depth);
+    for z in 0..depth {
+        for x in 0..width {
+            heights.push(generator.sample_height(origin.x as f64 + x as f64, origin.z as f64 + z as f64));
+        }
+    }
+    Heightmap { width, depth, heights }
+}
+
+pub fn write_obj(mesh: &Mesh, mut out: impl Write) -> io::Result<()> {
+    for v in mesh.vertices.chunks_exact(3) {
+        writeln!(out, "v {} {} {}", v[0], v[1], v[2])?;
+    }
+    for n in mesh.normals.chunks_exact(3) {
+        writeln!(out, "vn {} {} {}", n[0], n[1], n[2])?;
+    }
+    // OBJ indices start at 1
+    for tri in mesh.indices.chunks_exact(3) {
+        let (a, b, c) = (tri[0] + 1, tri[1] + 1, tri[2] + 1);
+        writeln!(out, "f {a}//{a} {b}//{b} {c}//{c}")?;
+    }
+    Ok(())
+}
+
+/// glTF document for `mesh` and the buffer it refers to: positions, then normals, then indices.
+fn gltf(mesh: &Mesh) -> (serde_json::Value, Vec<u8>) {
+    let mut buffer = Vec::with_capacity((mesh.vertices.len() + mesh.normals.len() + mesh.indices.len()) * 4);
+    buffer.extend(mesh.vertices.iter().flat_map(|v| v.to_le_bytes()));
+    buffer.extend(mesh.normals.iter().flat_map(|v| v.to_le_bytes()));
+    buffer.extend(mesh.indices.iter().flat_map(|i| i.to_le_bytes()));
+
+    let vertex_bytes = mesh.vertices.len() * 4;
+    let index_bytes = mesh.indices.len() * 4;
+    let (mut min, mut max) = ([f32::MAX; 3], [f32::MIN; 3]);
+    for v in mesh.vertices.chunks_exact(3) {
+        for axis in 0..3 {
+            min[axis] = min[axis].min(v[axis]);
+            max[axis] = max[axis].max(v[axis]);
+        }
+    }
+
+    let document = json!({
+        "asset": { "version": "2.0", "generator": "realm-tools" },
+        "scene": 0,
+        "scenes": [{ "nodes": [0] }],
+        "nodes": [{ "mesh": 0, "name": "terrain" }],
+        "meshes": [{
+            "primitives": [{ "attributes": { "POSITION": 0, "NORMAL": 1 }, "indices": 2 }],
+        }],
+        "buffers": [{ "byteLength": buffer.len() }],
+        "bufferViews": [
+            { "buffer": 0, "byteOffset": 0, "byteLength": vertex_bytes, "target": 34962 },
+            { "buffer": 0, "byteOffset": vertex_bytes, "byteLength": vertex_bytes, "target": 34962 },
+            { "buffer": 0, "byteOffset": vertex_bytes * 2, "byteLength": index_bytes, "target": 34963 },
+        ],
+        "accessors": [
+            // 5126 is FLOAT, 5125 UNSIGNED_INT
+            { "bufferView": 0, "componentType": 5126, "count": mesh.vertices.len() / 3, "type": "VEC3", "min": min, "max": max },
+            { "bufferView": 1, "componentType": 5126, "count": mesh.normals.len() / 3, "type": "VEC3" },
+            { "bufferView": 2, "componentType": 5125, "count": mesh.indices.len(), "type": "SCALAR" },
+        ],
+    });
+    (document, buffer)
+}
+
+/// A self-contained .gltf with the buffer embedded as a data URI.
+pub fn write_gltf(mesh: &Mesh, mut out: impl Write) -> io::Result<()> {
+    let (mut document, buffer) = gltf(mesh);
+    let data = base64::engine::general_purpose::STANDARD.encode(&buffer);
+    document["buffers"][0]["uri"] = json!(format!("data:application/octet-stream;base64,{}", data));
+    out.write_all(serde_json::to_string(&document)?.as_bytes())
+}
+
+pub fn write_glb(mesh: &Mesh, mut out: impl Write) -> io::Result<()> {
+    let (document, mut buffer) = gltf(mesh);
+    // both chunks must be 4-byte aligned: JSON is padded with spaces, binary with zeros
+    let mut json = serde_json::to_vec(&document)?;
+    json.resize(json.len().next_multiple_of(4), b' ');
+    buffer.resize(buffer.len().next_multiple_of(4), 0);
+
+    let total = 12 + 8 + json.len() + 8 + buffer.len();
+    out.write_all(b"glTF")?;
+    out.write_all(&2u32.to_le_bytes())?;
+    out.write_all(&(total as u32).to_le_bytes())?;
+    out.write_all(&(json.len() as u32).to_le_bytes())?;
+    out.write_all(b"JSON")?;
+    out.write_all(&json)?;
+    out.write_all(&(buffer.len() as u32).to_le_bytes())?;
+    out.write_all(b"BIN\0")?;
+    out.write_all(&buffer)
+}
@@ -0,0 +1,173 @@
+// src/heightmap.rs
+
+use std::fs::{self, File};
+use std::path::Path;
+
+use clap::ValueEnum;
+use realm_backend::terrain::coords::{XZCoords, CHUNK_SIZE};
+use realm_backend::terrain::import::IMPORTED_TILE_DIM;
+
+/// SRTM marks missing samples with this value.
+const HGT_VOID: i16 = i16::MIN;
+
+#[derive(Clone, Copy, Debug, PartialEq, ValueEnum)]
+pub enum HeightmapFormat {
+    /// 16-bit greyscale PNG.
+    Png,
+    /// Headerless little-endian u16 samples in a square grid.
+    R16,
+    /// SRTM tile: big-endian i16 metres in a square grid.
+    Hgt,
+}
+
+impl HeightmapFormat {
+    pub fn from_path(path: &Path) -> Option<Self> {
+        match path.extension()?.to_str()?.to_ascii_lowercase().as_str() {
+            "png" => Some(Self::Png),
+            "r16" | "raw" => Some(Self::R16),
+            "hgt" => Some(Self::Hgt),
+            _ => None,
+        }
+    }
+}
+
+/// How raw samples become heights in blocks.
+#[derive(Clone, Copy, Debug)]
+pub struct HeightScale {
+    /// Height of a 0 sample in 16-bit formats.
+    pub min_height: f32,
+    /// Height of a 65535 sample in 16-bit formats.
+    pub max_height: f32,
+    /// Blocks per metre for SRTM data.
+    pub vertical_scale: f32,
+}
+
+/// A grid of heights in blocks with one sample per world block, row-major by z.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Heightmap {
+    pub width: usize,
+    pub depth: usize,
+    pub heights: Vec<f32>,
+}
+
+impl Heightmap {
+    pub fn from_u16(width: usize, depth: usize, samples: &[u16], min_height: f32, max_height: f32) -> Self {
+        let heights = samples.iter()
+            .map(|&s| min_height + (max_height - min_height) * s as f32 / u16::MAX as f32)
+            .collect();
+        Self { width, depth, heights }
+    }
+
+    /// Sample at `(x, z)`, clamped to the edge of the map.
+    pub fn get(&self, x: i32, z: i32) -> f32 {
+        let x = x.clamp(0, self.width as i32 - 1) as usize;
+        let z = z.clamp(0, self.depth as i32 - 1) as usize;
+        self.heights[z * self.width + x]
+    }
+
+    /// Chunks covered by the map when its first sample lands on the corner of `origin`.
+    pub fn chunk_count(&self) -> (i32, i32) {
+        let count = |samples: usize| ((samples as i32 - 1 + CHUNK_SIZE - 1) / CHUNK_SIZE).max(1);
+        (count(self.width), count(self.depth))
+    }
+
+    /// Slices the map into `imported_tile` rows. Each tile holds its chunk's corners plus one
+    /// sample of padding per side; samples past the edge of the map repeat the edge.
+    pub fn tiles(&self, origin: XZCoords) -> Vec<(XZCoords, Vec<f32>)> {
+        let (chunks_x, chunks_z) = self.chunk_count();
+        let mut tiles = Vec::with_capacity((chunks_x * chunks_z) as usize);
+        for cz in 0..chunks_z {
+            for cx in 0..chunks_x {
+                let mut heights = Vec::with_capacity(IMPORTED_TILE_DIM * IMPORTED_TILE_DIM);
+                for z in -1..=CHUNK_SIZE + 1 {
+                    for x in -1..=CHUNK_SIZE + 1 {
+                        heights.push(self.get(cx * CHUNK_SIZE + x, cz * CHUNK_SIZE + z));
+                    }
+                }
+                tiles.push((XZCoords { x: origin.x + cx, z: origin.z + cz }, heights));
+            }
+        }
+        tiles
+    }
+}
+
+/// Writes the map as a 16-bit greyscale PNG stretched over its full height range, and returns
+/// that range so the image can be imported again with the same `--min-height`/`--max-height`.
+pub fn encode_png16(heightmap: &Heightmap, writer: impl std::io::Write) -> Result<(f32, f32), String> {
+    let min = heightmap.heights.iter().copied().fold(f32::MAX, f32::min);
+    let max = heightmap.heights.iter().copied().fold(f32::MIN, f32::max);
+    let range = (max - min).max(f32::EPSILON);
+    let data: Vec<u8> = heightmap.heights.iter()
+        .map(|h| ((h - min) / range * u16::MAX as f32).round() as u16)
+        .flat_map(u16::to_be_bytes)
+        .collect();
+
+    let mut encoder = png::Encoder::new(writer, heightmap.width as u32, heightmap.depth as u32);
+    encoder.set_color(png::ColorType::Grayscale);
+    encoder.set_depth(png::BitDepth::Sixteen);
+    encoder.write_header()
+        .and_then(|mut writer| writer.write_image_data(&data))
+        .map_err(|e| e.to_string())?;
+    Ok((min, max))
+}
+
+pub fn load_heightmap(path: &Path, format: HeightmapFormat, scale: HeightScale) -> Result<Heightmap, String> {
+    match format {
+        HeightmapFormat::Png => {
+            let file = File::open(path).map_err(|e| format!("{}: {}", path.display(), e))?;
+            decode_png16(file, scale)
+        }
+        HeightmapFormat::R16 => parse_r16(&read(path)?, scale),
+        HeightmapFormat::Hgt => parse_hgt(&read(path)?, scale),
+    }
+}
+
+fn read(path: &Path) -> Result<Vec<u8>, String> {
+    fs::read(path).map_err(|e| format!("{}: {}", path.display(), e))
+}
+
+pub fn decode_png16(reader: impl std::io::Read, scale: HeightScale) -> Result<Heightmap, String> {
+    let mut reader = png::Decoder::new(reader).read_info().map_err(|e| e.to_string())?;
+    let mut buffer = vec![0; reader.output_buffer_size()];
+    let info = reader.next_frame(&mut buffer).map_err(|e| e.to_string())?;
+    if info.color_type != png::ColorType::Grayscale || info.bit_depth != png::BitDepth::Sixteen {
+        return Err(format!("expected a 16-bit greyscale PNG, got {:?} {:?}", info.bit_depth, info.color_type));
+    }
+
+    // PNG stores 16-bit samples big-endian
+    let samples: Vec<u16> = buffer[..info.buffer_size()].chunks_exact(2)
+        .map(|b| u16::from_be_bytes([b[0], b[1]]))
+        .collect();
+    let (width, depth) = (info.width as usize, info.height as usize);
+    Ok(Heightmap::from_u16(width, depth, &samples, scale.min_height, scale.max_height))
+}
+
+pub fn parse_r16(bytes: &[u8], scale: HeightScale) -> Result<Heightmap, String> {
+    let size = square_size(bytes)?;
+    let samples: Vec<u16> = bytes.chunks_exact(2)
+        .map(|b| u16::from_le_bytes([b[0], b[1]]))
+        .collect();
+    Ok(Heightmap::from_u16(size, size, &samples, scale.min_height, scale.max_height))
+}
+
+/// Voids become sea level.
+pub fn parse_hgt(bytes: &[u8], scale: HeightScale) -> Result<Heightmap, String> {
+    let size = square_size(bytes)?;
+    let heights = bytes.chunks_exact(2)
+        .map(|b| match i16::from_be_bytes([b[0], b[1]]) {
+            HGT_VOID => 0.0,
+            metres => metres as f32 * scale.vertical_scale,
+        })
+        .collect();
+    Ok(Heightmap { width: size, depth: size, heights })
+}
+
+/// Side length of a headerless square grid of 16-bit samples.
+fn square_size(bytes: &[u8]) -> Result<usize, String> {
+    let samples = bytes.len() / 2;
+    let size = (samples as f64).sqrt().round() as usize;
+    if !bytes.len().is_multiple_of(2) || size < 2 || size * size != samples {
+        return Err(format!("{} bytes is not a square grid of 16-bit samples", bytes.len()));
+    }
+    Ok(size)
+}
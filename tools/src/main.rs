@@ -0,0 +1,175 @@
+// src/main.rs
+
+use std::fs::File;
+use std::io::BufWriter;
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+
+use clap::{Args, Parser, Subcommand};
+use realm_backend::terrain::coords::XZCoords;
+use realm_backend::terrain::generator::HeightmapGenerator;
+use realm_backend::terrain::world::DEFAULT_SEED;
+
+mod export;
+mod heightmap;
+mod upload;
+#[cfg(test)]
+mod tests;
+
+use export::{region_heights, region_mesh, write_glb, write_gltf, write_obj, Region};
+use heightmap::{encode_png16, load_heightmap, HeightScale, HeightmapFormat};
+use upload::Target;
+
+/// Offline tools for realm terrain.
+#[derive(Parser)]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Slices a heightmap into chunk tiles and uploads them to a realm.
+    Import(ImportArgs),
+    /// Generates a rectangle of chunks and writes it as a mesh and/or a heightmap image.
+    Export(ExportArgs),
+}
+
+#[derive(Args)]
+struct ImportArgs {
+    /// 16-bit greyscale PNG, raw .r16 or SRTM .hgt file.
+    file: PathBuf,
+    /// Database name or identity to upload to.
+    database: String,
+    /// Server to upload to, as understood by `spacetime call --server`.
+    #[arg(long)]
+    server: Option<String>,
+    /// File format; guessed from the extension if not given.
+    #[arg(long, value_enum)]
+    format: Option<HeightmapFormat>,
+    /// Chunk whose corner the first sample of the file lands on.
+    #[arg(long, default_value_t = 0, allow_negative_numbers = true)]
+    origin_x: i32,
+    #[arg(long, default_value_t = 0, allow_negative_numbers = true)]
+    origin_z: i32,
+    /// Height of a 0 sample in 16-bit formats.
+    #[arg(long, default_value_t = 0.0, allow_negative_numbers = true)]
+    min_height: f32,
+    /// Height of a 65535 sample in 16-bit formats.
+    #[arg(long, default_value_t = 64.0, allow_negative_numbers = true)]
+    max_height: f32,
+    /// Blocks per metre for .hgt files. The default keeps 1 arc-second (~30 m) data in proportion.
+    #[arg(long, default_value_t = 1.0 / 30.0)]
+    vertical_scale: f32,
+    /// Slice the file and report the tiles without uploading them.
+    #[arg(long)]
+    dry_run: bool,
+}
+
+#[derive(Args)]
+struct ExportArgs {
+    /// World seed to generate from. Player edits and custom terrain params are not included.
+    #[arg(long, default_value_t = DEFAULT_SEED)]
+    seed: u32,
+    /// One corner chunk of the region, as `x,z`.
+    #[arg(long, value_parser = parse_coords, allow_hyphen_values = true)]
+    from: XZCoords,
+    /// The opposite corner chunk, inclusive.
+    #[arg(long, value_parser = parse_coords, allow_hyphen_values = true)]
+    to: XZCoords,
+    /// Welded mesh to write: .glb, .gltf or .obj.
+    #[arg(long)]
+    mesh: Option<PathBuf>,
+    /// 16-bit greyscale PNG of the region's heights.
+    #[arg(long)]
+    heightmap: Option<PathBuf>,
+}
+
+fn parse_coords(value: &str) -> Result<XZCoords, String> {
+    let (x, z) = value.split_once(',').ok_or("expected `x,z`")?;
+    let parse = |v: &str| v.trim().parse::<i32>().map_err(|e| e.to_string());
+    Ok(XZCoords { x: parse(x)?, z: parse(z)? })
+}
+
+fn create(path: &Path) -> Result<BufWriter<File>, String> {
+    File::create(path)
+        .map(BufWriter::new)
+        .map_err(|e| format!("{}: {}", path.display(), e))
+}
+
+fn export(args: ExportArgs) -> Result<(), String> {
+    if args.mesh.is_none() && args.heightmap.is_none() {
+        return Err("nothing to export; pass --mesh and/or --heightmap".into());
+    }
+    let generator = HeightmapGenerator::new(args.seed);
+    let region = Region::new(args.from, args.to);
+
+    if let Some(path) = &args.mesh {
+        let mesh = region_mesh(&generator, region);
+        let extension = path.extension().and_then(|e| e.to_str()).unwrap_or_default().to_ascii_lowercase();
+        let out = create(path)?;
+        match extension.as_str() {
+            "glb" => write_glb(&mesh, out),
+            "gltf" => write_gltf(&mesh, out),
+            "obj" => write_obj(&mesh, out),
+            _ => return Err(format!("{}: mesh must be .glb, .gltf or .obj", path.display())),
+        }
+        .map_err(|e| format!("{}: {}", path.display(), e))?;
+        println!(
+            "wrote {} vertices and {} triangles to {}",
+            mesh.vertices.len() / 3, mesh.indices.len() / 3, path.display(),
+        );
+    }
+
+    if let Some(path) = &args.heightmap {
+        let heightmap = region_heights(&generator, region);
+        let (min, max) = encode_png16(&heightmap, create(path)?)?;
+        println!(
+            "wrote {}x{} heightmap to {} (--min-height {} --max-height {})",
+            heightmap.width, heightmap.depth, path.display(), min, max,
+        );
+    }
+    Ok(())
+}
+
+fn import(args: ImportArgs) -> Result<(), String> {
+    let format = args.format
+        .or_else(|| HeightmapFormat::from_path(&args.file))
+        .ok_or("unknown heightmap format; pass --format")?;
+    let scale = HeightScale {
+        min_height: args.min_height,
+        max_height: args.max_height,
+        vertical_scale: args.vertical_scale,
+    };
+    let heightmap = load_heightmap(&args.file, format, scale)?;
+    let origin = XZCoords { x: args.origin_x, z: args.origin_z };
+    let tiles = heightmap.tiles(origin);
+    let (chunks_x, chunks_z) = heightmap.chunk_count();
+    println!(
+        "{}x{} samples -> {}x{} chunks from ({}, {})",
+        heightmap.width, heightmap.depth, chunks_x, chunks_z, origin.x, origin.z,
+    );
+    if args.dry_run {
+        return Ok(());
+    }
+
+    let target = Target { server: args.server, database: args.database };
+    for (i, (grid, heights)) in tiles.iter().enumerate() {
+        target.upload_tile(*grid, heights)?;
+        println!("uploaded tile {}/{} ({}, {})", i + 1, tiles.len(), grid.x, grid.z);
+    }
+    println!("done; set the world's terrain source to Imported to generate chunks from it");
+    Ok(())
+}
+
+fn main() -> ExitCode {
+    let result = match Cli::parse().command {
+        Command::Import(args) => import(args),
+        Command::Export(args) => export(args),
+    };
+    if let Err(error) = result {
+        eprintln!("error: {}", error);
+        return ExitCode::FAILURE;
+    }
+    ExitCode::SUCCESS
+}
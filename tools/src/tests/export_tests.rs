@@ -0,0 +1,66 @@
+use std::collections::HashSet;
+
+use crate::export::{region_heights, region_mesh, write_glb, write_obj, Region};
+use crate::heightmap::{decode_png16, encode_png16, HeightScale};
+use realm_backend::terrain::coords::{XZCoords, CHUNK_SIZE};
+use realm_backend::terrain::generator::HeightmapGenerator;
+use approx::assert_relative_eq;
+
+const CS: usize = CHUNK_SIZE as usize;
+
+fn region() -> Region {
+    Region::new(XZCoords { x: 1, z: 0 }, XZCoords { x: -1, z: 1 })
+}
+
+#[test]
+fn test_region_mesh_is_welded() {
+    let mesh = region_mesh(&HeightmapGenerator::new(42), region());
+
+    // 3x2 chunks share their border vertices
+    assert_eq!(mesh.vertices.len() / 3, (3 * CS + 1) * (2 * CS + 1));
+    assert_eq!(mesh.indices.len() / 3, 3 * 2 * CS * CS * 2);
+    let used: HashSet<u32> = mesh.indices.iter().copied().collect();
+    assert_eq!(used.len(), mesh.vertices.len() / 3, "Every vertex is used");
+}
+
+#[test]
+fn test_region_heights_match_generator() {
+    let generator = HeightmapGenerator::new(42);
+    let heightmap = region_heights(&generator, region());
+
+    assert_eq!((heightmap.width, heightmap.depth), (3 * CS + 1, 2 * CS + 1));
+    // the first sample is the corner of chunk (-1, 0)
+    assert_eq!(heightmap.get(0, 0), generator.sample_height(-(CS as f64), 0.0));
+    assert_eq!(heightmap.get(5, 7), generator.sample_height(5.0 - CS as f64, 7.0));
+}
+
+#[test]
+fn test_heightmap_png_round_trip() {
+    let heightmap = region_heights(&HeightmapGenerator::new(42), region());
+    let mut bytes = Vec::new();
+    let (min_height, max_height) = encode_png16(&heightmap, &mut bytes).unwrap();
+
+    let scale = HeightScale { min_height, max_height, vertical_scale: 1.0 };
+    let decoded = decode_png16(bytes.as_slice(), scale).unwrap();
+    assert_eq!((decoded.width, decoded.depth), (heightmap.width, heightmap.depth));
+    for (a, b) in decoded.heights.iter().zip(&heightmap.heights) {
+        assert_relative_eq!(a, b, epsilon = 1e-2);
+    }
+}
+
+#[test]
+fn test_mesh_writers() {
+    let mesh = region_mesh(&HeightmapGenerator::new(42), Region::new(XZCoords { x: 0, z: 0 }, XZCoords { x: 0, z: 0 }));
+
+    let mut obj = Vec::new();
+    write_obj(&mesh, &mut obj).unwrap();
+    let obj = String::from_utf8(obj).unwrap();
+    assert_eq!(obj.lines().filter(|l| l.starts_with("v ")).count(), mesh.vertices.len() / 3);
+    assert_eq!(obj.lines().filter(|l| l.starts_with("f ")).count(), mesh.indices.len() / 3);
+
+    let mut glb = Vec::new();
+    write_glb(&mesh, &mut glb).unwrap();
+    assert_eq!(&glb[0..4], b"glTF");
+    assert_eq!(u32::from_le_bytes(glb[8..12].try_into().unwrap()) as usize, glb.len());
+    assert_eq!(glb.len() % 4, 0);
+}
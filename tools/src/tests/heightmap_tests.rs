@@ -0,0 +1,73 @@
+use crate::heightmap::{decode_png16, parse_hgt, parse_r16, HeightScale, Heightmap};
+use realm_backend::terrain::coords::{XZCoords, CHUNK_SIZE};
+use realm_backend::terrain::import::IMPORTED_TILE_DIM;
+use approx::assert_relative_eq;
+
+const SCALE: HeightScale = HeightScale { min_height: -10.0, max_height: 10.0, vertical_scale: 0.5 };
+
+fn ramp(size: usize) -> Heightmap {
+    let heights = (0..size * size).map(|i| (i % size) as f32 + 1000.0 * (i / size) as f32).collect();
+    Heightmap { width: size, depth: size, heights }
+}
+
+#[test]
+fn test_parse_r16() {
+    let bytes: Vec<u8> = [0u16, u16::MAX, 0, u16::MAX].iter().flat_map(|s| s.to_le_bytes()).collect();
+    let heightmap = parse_r16(&bytes, SCALE).unwrap();
+
+    assert_eq!((heightmap.width, heightmap.depth), (2, 2));
+    assert_eq!(heightmap.heights, vec![-10.0, 10.0, -10.0, 10.0]);
+    assert!(parse_r16(&bytes[..6], SCALE).is_err(), "Not a square grid");
+}
+
+#[test]
+fn test_parse_hgt() {
+    let bytes: Vec<u8> = [100i16, -20, i16::MIN, 8].iter().flat_map(|s| s.to_be_bytes()).collect();
+    let heightmap = parse_hgt(&bytes, SCALE).unwrap();
+
+    assert_eq!(heightmap.heights, vec![50.0, -10.0, 0.0, 4.0]);
+}
+
+#[test]
+fn test_decode_png16() {
+    let mut bytes = Vec::new();
+    let mut encoder = png::Encoder::new(&mut bytes, 2, 1);
+    encoder.set_color(png::ColorType::Grayscale);
+    encoder.set_depth(png::BitDepth::Sixteen);
+    let data: Vec<u8> = [0u16, 32768].iter().flat_map(|s| s.to_be_bytes()).collect();
+    encoder.write_header().unwrap().write_image_data(&data).unwrap();
+
+    let heightmap = decode_png16(bytes.as_slice(), SCALE).unwrap();
+    assert_eq!((heightmap.width, heightmap.depth), (2, 1));
+    assert_relative_eq!(heightmap.heights[1], 0.0, epsilon = 1e-3);
+}
+
+#[test]
+fn test_tiles_cover_map() {
+    // 33 samples fill exactly one chunk, one more spills into a second
+    assert_eq!(ramp(CHUNK_SIZE as usize + 1).chunk_count(), (1, 1));
+    assert_eq!(ramp(CHUNK_SIZE as usize + 2).chunk_count(), (2, 2));
+
+    let tiles = ramp(CHUNK_SIZE as usize + 2).tiles(XZCoords { x: -1, z: 3 });
+    let grids: Vec<XZCoords> = tiles.iter().map(|(grid, _)| *grid).collect();
+    assert_eq!(grids, vec![
+        XZCoords { x: -1, z: 3 }, XZCoords { x: 0, z: 3 },
+        XZCoords { x: -1, z: 4 }, XZCoords { x: 0, z: 4 },
+    ]);
+    assert!(tiles.iter().all(|(_, heights)| heights.len() == IMPORTED_TILE_DIM * IMPORTED_TILE_DIM));
+}
+
+#[test]
+fn test_tile_padding() {
+    let heightmap = ramp(80);
+    let tiles = heightmap.tiles(XZCoords { x: 0, z: 0 });
+    let sample = |tile: usize, x: i32, z: i32| tiles[tile].1[(z + 1) as usize * IMPORTED_TILE_DIM + (x + 1) as usize];
+
+    assert_eq!(sample(0, 0, 0), heightmap.get(0, 0));
+    // padding before the map repeats its edge
+    assert_eq!(sample(0, -1, -1), heightmap.get(0, 0));
+    // padding past the chunk comes from the next chunk's samples
+    assert_eq!(sample(0, CHUNK_SIZE + 1, 5), heightmap.get(CHUNK_SIZE + 1, 5));
+    // neighbouring tiles agree on their shared border
+    assert_eq!(sample(0, CHUNK_SIZE, 7), sample(1, 0, 7));
+}
@@ -0,0 +1,2 @@
+mod heightmap_tests;
+mod export_tests;
\ No newline at end of file
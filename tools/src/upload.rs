@@ -0,0 +1,38 @@
+// src/upload.rs
+
+use std::process::Command;
+
+use realm_backend::terrain::coords::XZCoords;
+
+/// Where `spacetime call` should send reducer calls.
+pub struct Target {
+    pub server: Option<String>,
+    pub database: String,
+}
+
+impl Target {
+    /// Calls a reducer through the `spacetime` CLI with JSON arguments.
+    pub fn call(&self, reducer: &str, args: &[String]) -> Result<(), String> {
+        let mut command = Command::new("spacetime");
+        command.arg("call");
+        if let Some(server) = &self.server {
+            command.arg("--server").arg(server);
+        }
+        command.arg(&self.database).arg(reducer).args(args);
+
+        let output = command.output().map_err(|e| format!("failed to run `spacetime call`: {}", e))?;
+        if !output.status.success() {
+            return Err(format!("{} failed: {}", reducer, String::from_utf8_lossy(&output.stderr).trim()));
+        }
+        Ok(())
+    }
+
+    /// Uploads one tile with the `upload_imported_tile` reducer.
+    pub fn upload_tile(&self, grid: XZCoords, heights: &[f32]) -> Result<(), String> {
+        let heights = heights.iter().map(f32::to_string).collect::<Vec<_>>().join(",");
+        self.call("upload_imported_tile", &[
+            format!("{{\"x\":{},\"z\":{}}}", grid.x, grid.z),
+            format!("[{}]", heights),
+        ])
+    }
+}